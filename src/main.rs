@@ -4,6 +4,7 @@ use std::{
     num::{ParseIntError, TryFromIntError},
 };
 
+use runner::{Day, Runner};
 use thiserror::Error;
 
 mod problem01;
@@ -14,7 +15,12 @@ mod problem05;
 mod problem06;
 mod problem07;
 mod problem08;
+mod runner;
+// The shared structures and utilities are general purpose libraries built up ahead of
+// the days which need them, so not everything in them has a caller yet
+#[allow(dead_code)]
 mod structures;
+#[allow(dead_code)]
 mod utils;
 
 #[derive(Debug, Error)]
@@ -34,6 +40,34 @@ pub enum Error {
     #[error(transparent)]
     GridInitFailure(#[from] structures::grid::Error),
 
+    /// A map or instruction held something which isn't a direction
+    #[error(transparent)]
+    DirectionError(#[from] structures::coordinate::Error),
+
+    /// The guard never leaves the map. `length` counts the moves and turns in one lap
+    #[error("Guard is stuck in a loop of {length} steps entered at {entry:?} facing {direction}")]
+    GuardCycle {
+        entry: structures::coordinate::Coordinate<usize>,
+        length: usize,
+        direction: structures::coordinate::Direction,
+    },
+
+    /// Ordering rules which contradict each other
+    #[error(transparent)]
+    OrderingError(#[from] structures::ordering::Error),
+
+    /// A sort was asked to order values which have no consistent order
+    #[error(transparent)]
+    SortError(#[from] utils::Error),
+
+    /// Input which isn't the expected number of whitespace separated columns
+    #[error(transparent)]
+    ColumnError(#[from] utils::columns::Error),
+
+    /// A statistic which couldn't be calculated for the values given
+    #[error(transparent)]
+    StatisticsError(#[from] utils::statistics::Error),
+
     /// Failed to convert usize to i32
     #[error(transparent)]
     TryFromIntError(#[from] TryFromIntError),
@@ -78,18 +112,24 @@ pub fn parse_input(file_name: &str) -> Result<Vec<String>, Error> {
 }
 
 fn main() {
-    let _ = problem01::problem01();
-    let _ = problem02::problem02();
-    let _ = problem03::problem03();
-    let _ = problem04::problem04();
-    let _ = problem05::problem05();
-    // Slow! Not running until I optimize part2
-    // let _ = problem06::problem06();
-    // Slow!
-    let _ = problem07::problem07();
-    match problem08::problem08() {
-        Ok(_) => (),
-        Err(e) => println!("{e}"),
+    let runner = Runner::from_args(std::env::args());
+
+    let days: [Day; 8] = [
+        problem01::problem01,
+        problem02::problem02,
+        problem03::problem03,
+        problem04::problem04,
+        problem05::problem05,
+        problem06::problem06,
+        problem07::problem07,
+        problem08::problem08,
+    ];
+
+    for output in runner.days(&days) {
+        match output {
+            Ok(lines) => println!("{lines}"),
+            Err(e) => println!("{e}"),
+        }
     }
     println!("Hello, world!");
 }
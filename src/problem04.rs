@@ -2,7 +2,7 @@ use strum::VariantArray;
 
 use crate::structures::coordinate::{Coordinate, Direction};
 use crate::structures::grid::Grid;
-use crate::{parse_input, Error};
+use crate::{parse_input, runner::Runner, Error};
 
 fn problem04_part1(grid: &Grid<usize, char>) -> Result<i32, Error> {
     let mut count = 0;
@@ -94,15 +94,10 @@ fn init_grid_from_input(input: Vec<String>) -> Result<Grid<usize, char>, Error>
     Ok(grid)
 }
 
-pub fn problem04() -> Result<(), Error> {
+pub fn problem04(runner: &Runner) -> Result<String, Error> {
     let input = parse_input("input/problem_04.txt")?;
 
     let grid = init_grid_from_input(input)?;
 
-    let solution_one = problem04_part1(&grid)?;
-    println!("Problem 04 Part 1: {solution_one}");
-    let solution_two = problem04_part2(&grid)?;
-    println!("Problem 04 Part 2: {solution_two}");
-
-    Ok(())
+    runner.parts(4, &grid, problem04_part1, problem04_part2)
 }
@@ -1,135 +1,298 @@
-use std::collections::{HashMap, HashSet};
-
 use crate::{
     parse_input,
+    runner::Runner,
     structures::{
-        coordinate::{Coordinate, Direction},
+        coordinate::{CardinalDirection, Coordinate, Direction},
         grid::Grid,
+        walker::{Action, Termination, Walker},
     },
+    utils::cycle::find_cycle,
     Error,
 };
 
 fn problem06_part1(input: &Input) -> Result<i32, Error> {
-    match traverse_grid_from_point(&input.grid, input.starting_pos)? {
-        // This should be an error but that'll be a TODO!
-        Termination::Cycle(_) => todo!(),
+    let mut walker = Walker::new(
+        &input.grid,
+        input.starting_pos,
+        input.starting_direction,
+        turn_right_at_obstacles,
+    )?;
 
+    match walker.run() {
+        Termination::Cycle {
+            entry,
+            length,
+            direction,
+        } => Err(Error::GuardCycle {
+            entry,
+            length,
+            direction,
+        }),
         // What we expect!
-        Termination::OutOfBounds(path) => Ok(path.len().try_into()?),
+        _ => Ok(walker.positions_visited().try_into()?),
     }
 }
 
-fn problem06_part2(input: &mut Input) -> Result<i32, Error> {
-    let path = match traverse_grid_from_point(&mut input.grid, input.starting_pos)? {
-        // This should be an error but that'll be a TODO!
-        Termination::Cycle(_) => todo!(),
+/// The guard walks forward until something is in the way, then turns right
+fn turn_right_at_obstacles(
+    _: Coordinate<usize>,
+    direction: Direction,
+    ahead: Option<char>,
+) -> Action {
+    match ahead {
+        Some('#') => Action::Turn(direction.turn_right()),
+        _ => Action::Move,
+    }
+}
 
-        // What we expect!
-        Termination::OutOfBounds(path) => path,
-    };
+/// Count the cells where one extra obstacle would trap the guard in a loop
+fn problem06_part2(input: &Input) -> Result<i32, Error> {
+    let lab = Lab::new(&input.grid)?;
+    let start = lab.cell(input.starting_pos);
+    let facing = Lab::facing(input.starting_direction.try_into()?);
 
+    let mut placed = vec![false; lab.obstacles.len()];
+    placed[start] = true;
+    let mut visited = StateSet::new(lab.obstacles.len());
     let mut count = 0;
-    for position in path.keys() {
-        // Ignore the starting spot
-        if position == &input.starting_pos {
-            continue;
-        }
-
-        // This will work
-        let _ = input.grid.set(position.row, position.column, '#');
 
-        // Have to traverse
-        match traverse_grid_from_point(&input.grid, input.starting_pos)? {
-            Termination::Cycle(_) => count += 1,
-            Termination::OutOfBounds(_) => (),
-        }
+    // Walk the original route. The first time the guard is about to step on to a cell,
+    // block it and probe from right here since the route so far is unchanged
+    let mut current = (start, facing);
+    let cycle = find_cycle(current, |&(cell, facing)| {
+        let next = lab.step(cell, facing)?;
+        current = if lab.obstacles[next] {
+            (cell, (facing + 1) % 4)
+        } else {
+            if !placed[next] {
+                placed[next] = true;
+                if lab.loops_with_obstacle(cell, facing, next, &mut visited) {
+                    count += 1;
+                }
+            }
+            (next, facing)
+        };
+        Some(current)
+    });
 
-        // This will work
-        let _ = input.grid.set(position.row, position.column, '.');
+    // The unmodified route has to leave the map for the question to make sense. The
+    // last state reached is the one which repeated
+    if let Some(cycle) = cycle {
+        let (cell, facing) = current;
+        return Err(Error::GuardCycle {
+            entry: lab.coordinate(cell),
+            length: cycle.length,
+            direction: Lab::FACINGS[facing].into(),
+        });
     }
 
     Ok(count)
 }
 
-struct Input {
-    grid: Grid<usize, char>,
-    starting_pos: Coordinate<usize>,
+/// Dense copy of the map for walking it many times. Cells are numbered row major and
+/// facings run clockwise from North so turning right is adding one
+struct Lab {
+    rows: usize,
+    columns: usize,
+    obstacles: Vec<bool>,
+
+    /// For each `cell * 4 + facing`, the cell the guard stops on in front of the next
+    /// obstacle, or `EXIT` if it walks off the map first
+    jumps: Vec<usize>,
 }
 
-enum Termination {
-    /// The grid is re-visits a point in the same direction.
-    Cycle(HashMap<Coordinate<usize>, HashSet<Direction>>),
+impl Lab {
+    const EXIT: usize = usize::MAX;
 
-    /// The grid traverses off of the plane.
-    OutOfBounds(HashMap<Coordinate<usize>, HashSet<Direction>>),
-}
+    const FACINGS: [CardinalDirection; 4] = [
+        CardinalDirection::North,
+        CardinalDirection::East,
+        CardinalDirection::South,
+        CardinalDirection::West,
+    ];
 
-fn traverse_grid_from_point(
-    grid: &Grid<usize, char>,
-    starting_pos: Coordinate<usize>,
-) -> Result<Termination, Error> {
-    let mut direction = Direction::North;
-    let mut position = starting_pos;
-
-    let mut visited: HashMap<Coordinate<usize>, HashSet<Direction>> = HashMap::new();
-
-    // This loop will terminate via a return
-    loop {
-        // Mark that we've been facing this direction at this position
-        if visited.contains_key(&position)
-            && visited
-                .get(&position)
-                .expect("Initialized")
-                .contains(&direction)
-        {
-            return Ok(Termination::Cycle(visited));
-        } else if visited.contains_key(&position) {
-            visited
-                .get_mut(&position)
-                .expect("Initialized")
-                .insert(direction);
-        } else {
-            let mut direction_set = HashSet::new();
-            direction_set.insert(direction);
-            visited.insert(position, direction_set);
+    fn new(grid: &Grid<usize, char>) -> Result<Self, Error> {
+        let (rows, columns) = (grid.rows(), grid.columns());
+        let mut obstacles = vec![false; rows * columns];
+        for row in 0..rows {
+            for column in 0..columns {
+                obstacles[row * columns + column] = grid.get(row, column)? == Some('#');
+            }
         }
 
-        // First attempt to move forward
-        let next_position = match position.traverse(direction) {
-            Some(pos) => match grid.valid_index(pos.row, pos.column) {
-                true => pos,
-                // Left the grid in a positive index (Overflow).
-                false => return Ok(Termination::OutOfBounds(visited)),
-            },
+        let mut lab = Lab {
+            rows,
+            columns,
+            obstacles,
+            jumps: vec![Self::EXIT; rows * columns * 4],
+        };
 
-            // We've left the grid in an impossible index (Underflow).
-            None => return Ok(Termination::OutOfBounds(visited)),
+        for facing in 0..4 {
+            // Visit cells nearest the edge being walked towards first, so the jump
+            // from the next cell along is always known already
+            let mut cells: Vec<usize> = (0..rows * columns).collect();
+            if Self::FACINGS[facing] == CardinalDirection::South
+                || Self::FACINGS[facing] == CardinalDirection::East
+            {
+                cells.reverse();
+            }
+
+            for cell in cells {
+                lab.jumps[cell * 4 + facing] = match lab.step(cell, facing) {
+                    None => Self::EXIT,
+                    Some(next) if lab.obstacles[next] => cell,
+                    Some(next) => lab.jumps[next * 4 + facing],
+                };
+            }
+        }
+
+        Ok(lab)
+    }
+
+    fn cell(&self, coordinate: Coordinate<usize>) -> usize {
+        coordinate.row * self.columns + coordinate.column
+    }
+
+    fn coordinate(&self, cell: usize) -> Coordinate<usize> {
+        Coordinate::new(cell / self.columns, cell % self.columns)
+    }
+
+    fn facing(direction: CardinalDirection) -> usize {
+        Self::FACINGS
+            .iter()
+            .position(|d| *d == direction)
+            .expect("Every cardinal direction is listed")
+    }
+
+    /// The neighbouring cell, if it's on the map
+    fn step(&self, cell: usize, facing: usize) -> Option<usize> {
+        let (row, column) = (cell / self.columns, cell % self.columns);
+        let next = Coordinate::new(row, column).traverse(Self::FACINGS[facing].into())?;
+        (next.row < self.rows && next.column < self.columns).then(|| self.cell(next))
+    }
+
+    /// Where the guard stops walking from `cell`, taking the one added obstacle into
+    /// account. None when it walks off the map
+    fn stop(&self, cell: usize, facing: usize, obstacle: usize) -> Option<usize> {
+        let natural = self.jumps[cell * 4 + facing];
+
+        let (row, column) = (cell / self.columns, cell % self.columns);
+        let (obstacle_row, obstacle_column) = (obstacle / self.columns, obstacle % self.columns);
+        // How far ahead the added obstacle is, if it's in the guard's way at all
+        let distance = match Self::FACINGS[facing] {
+            CardinalDirection::North if obstacle_column == column && obstacle_row < row => {
+                row - obstacle_row
+            }
+            CardinalDirection::South if obstacle_column == column && obstacle_row > row => {
+                obstacle_row - row
+            }
+            CardinalDirection::West if obstacle_row == row && obstacle_column < column => {
+                column - obstacle_column
+            }
+            CardinalDirection::East if obstacle_row == row && obstacle_column > column => {
+                obstacle_column - column
+            }
+            _ => return (natural != Self::EXIT).then_some(natural),
         };
 
-        // The position is valid within the grid. We expect a value in all positions
-        let c = grid
-            .get(next_position.row, next_position.column)?
-            .expect("All indexes initialized");
-        if c == '#' {
-            // Rotate
-            direction = direction.rotate_90();
+        // Cells along a row or column are a fixed stride apart
+        let stride = match Self::FACINGS[facing] {
+            CardinalDirection::North | CardinalDirection::South => self.columns,
+            CardinalDirection::East | CardinalDirection::West => 1,
+        };
+        let natural_distance = match natural {
+            Self::EXIT => usize::MAX,
+            stop => stop.abs_diff(cell) / stride,
+        };
+
+        if distance <= natural_distance {
+            // Stop just short of the added obstacle
+            match cell < obstacle {
+                true => Some(obstacle - stride),
+                false => Some(obstacle + stride),
+            }
         } else {
-            // Traverse forward
-            position = next_position;
+            (natural != Self::EXIT).then_some(natural)
+        }
+    }
+
+    /// Whether a guard at `cell` facing `facing`, with an extra obstacle, ends up
+    /// walking in circles
+    fn loops_with_obstacle(
+        &self,
+        cell: usize,
+        facing: usize,
+        obstacle: usize,
+        visited: &mut StateSet,
+    ) -> bool {
+        visited.clear();
+
+        let (mut cell, mut facing) = (cell, facing);
+        // Only the turning points are recorded, a loop has to repeat one of them
+        while let Some(stop) = self.stop(cell, facing, obstacle) {
+            if !visited.insert(stop * 4 + facing) {
+                return true;
+            }
+            cell = stop;
+            facing = (facing + 1) % 4;
+        }
+        false
+    }
+}
+
+/// Dense bitset of `cell * 4 + facing` states. Clearing only resets the words which
+/// were written so it stays cheap when reused for many short walks
+struct StateSet {
+    bits: Vec<u64>,
+    touched: Vec<usize>,
+}
+
+impl StateSet {
+    fn new(cells: usize) -> Self {
+        StateSet {
+            bits: vec![0; (cells * 4).div_ceil(64)],
+            touched: Vec::new(),
+        }
+    }
+
+    /// False if the state was already present
+    fn insert(&mut self, state: usize) -> bool {
+        let (word, bit) = (state / 64, 1 << (state % 64));
+        if self.bits[word] & bit != 0 {
+            return false;
+        }
+        if self.bits[word] == 0 {
+            self.touched.push(word);
+        }
+        self.bits[word] |= bit;
+        true
+    }
+
+    fn clear(&mut self) {
+        for word in self.touched.drain(..) {
+            self.bits[word] = 0;
         }
     }
 }
 
+struct Input {
+    grid: Grid<usize, char>,
+    starting_pos: Coordinate<usize>,
+    starting_direction: Direction,
+}
+
 fn get_grid_from_input(lines: Vec<String>) -> Result<Input, Error> {
     let mut grid = Grid::new(lines.len(), lines[0].len());
     let mut starting_pos = Coordinate::new(0, 0);
+    let mut starting_direction = Direction::North;
 
     let mut row = 0;
     for line in lines {
         let mut column = 0;
         for character in line.chars() {
-            if character == '^' {
+            if let Ok(facing) = CardinalDirection::from_arrow(character) {
                 starting_pos = Coordinate::new(row, column);
+                starting_direction = facing.into();
                 // The starting position will not be written into the grid.
                 grid.set(row, column, '.')?;
             } else {
@@ -140,18 +303,143 @@ fn get_grid_from_input(lines: Vec<String>) -> Result<Input, Error> {
         row += 1;
     }
 
-    Ok(Input { grid, starting_pos })
+    Ok(Input {
+        grid,
+        starting_pos,
+        starting_direction,
+    })
 }
 
-pub fn problem06() -> Result<(), Error> {
+pub fn problem06(runner: &Runner) -> Result<String, Error> {
     let input = parse_input("input/problem_06.txt")?;
 
-    let mut parsed_input = get_grid_from_input(input)?;
+    let parsed_input = get_grid_from_input(input)?;
+
+    runner.parts(6, &parsed_input, problem06_part1, problem06_part2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: [&str; 10] = [
+        "....#.....",
+        ".........#",
+        "..........",
+        "..#.......",
+        ".......#..",
+        "..........",
+        ".#..^.....",
+        "........#.",
+        "#.........",
+        "......#...",
+    ];
 
-    let solution_one = problem06_part1(&parsed_input)?;
-    println!("Problem 06 Part 1: {solution_one}");
-    let solution_two = problem06_part2(&mut parsed_input)?;
-    println!("Problem 06 Part 2: {solution_two}");
+    fn input(lines: &[&str]) -> Result<Input, Error> {
+        get_grid_from_input(lines.iter().map(|line| line.to_string()).collect())
+    }
 
-    Ok(())
+    /// Add the obstacle to a copy of the map and walk it the slow way
+    fn loops_naively(
+        grid: &Grid<usize, char>,
+        start: Coordinate<usize>,
+        direction: Direction,
+        obstacle: Coordinate<usize>,
+    ) -> Result<bool, Error> {
+        let mut blocked = grid.clone();
+        blocked.set(obstacle.row, obstacle.column, '#')?;
+        let mut walker = Walker::new(&blocked, start, direction, turn_right_at_obstacles)?;
+        Ok(matches!(walker.run(), Termination::Cycle { .. }))
+    }
+
+    #[test]
+    fn example() -> Result<(), Error> {
+        let input = input(&EXAMPLE)?;
+        assert_eq!(41, problem06_part1(&input)?);
+        assert_eq!(6, problem06_part2(&input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn boxed_in_guard_is_reported() -> Result<(), Error> {
+        // Four obstacles keep the guard walking round a two by two square
+        let input = input(&[".#..", "...#", "#^..", "..#."])?;
+        for result in [problem06_part1(&input), problem06_part2(&input)] {
+            assert!(
+                matches!(
+                    result,
+                    Err(Error::GuardCycle {
+                        entry: Coordinate { row: 2, column: 1 },
+                        length: 8,
+                        direction: Direction::North,
+                    })
+                ),
+                "{result:?}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn probe_agrees_with_walking_from_the_start() -> Result<(), Error> {
+        let input = input(&EXAMPLE)?;
+        let lab = Lab::new(&input.grid)?;
+        let start = lab.cell(input.starting_pos);
+        let facing = Lab::facing(input.starting_direction.try_into()?);
+
+        // Every free cell, including ones behind the guard or off its route
+        let mut loops = 0;
+        let mut visited = StateSet::new(lab.obstacles.len());
+        for obstacle in (0..lab.obstacles.len()).filter(|c| *c != start && !lab.obstacles[*c]) {
+            let expected = loops_naively(
+                &input.grid,
+                input.starting_pos,
+                input.starting_direction,
+                lab.coordinate(obstacle),
+            )?;
+            assert_eq!(
+                expected,
+                lab.loops_with_obstacle(start, facing, obstacle, &mut visited),
+                "obstacle at {:?}",
+                lab.coordinate(obstacle)
+            );
+            loops += usize::from(expected);
+        }
+        assert_eq!(6, loops);
+        Ok(())
+    }
+
+    #[test]
+    fn probe_agrees_with_walking_from_every_state() -> Result<(), Error> {
+        // Obstacles in the same row or column as the guard, either side of it and past
+        // the natural stop all come up somewhere
+        let input = input(&["..#...", ".....#", "#.....", "...#..", ".#....", "....#."])?;
+        let lab = Lab::new(&input.grid)?;
+        let free: Vec<usize> = (0..lab.obstacles.len())
+            .filter(|c| !lab.obstacles[*c])
+            .collect();
+
+        let mut visited = StateSet::new(lab.obstacles.len());
+        for cell in &free {
+            for facing in 0..4 {
+                for obstacle in free.iter().filter(|c| *c != cell) {
+                    let expected = loops_naively(
+                        &input.grid,
+                        lab.coordinate(*cell),
+                        Lab::FACINGS[facing].into(),
+                        lab.coordinate(*obstacle),
+                    )?;
+                    assert_eq!(
+                        expected,
+                        lab.loops_with_obstacle(*cell, facing, *obstacle, &mut visited),
+                        "guard at {:?} facing {:?}, obstacle at {:?}",
+                        lab.coordinate(*cell),
+                        Lab::FACINGS[facing],
+                        lab.coordinate(*obstacle)
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
 }
@@ -1,30 +1,45 @@
 use std::fmt::Display;
 
-use crate::{parse_input, Error};
-
-/// Is the list of pages correct? If so what is the middle page?
-fn problem07_part1(input: &Input) -> Result<u64, Error> {
-    let mut count = 0;
-
-    for equation in &input.equations {
-        if equation.solvable() {
-            count += equation.result;
-        }
-    }
-
-    Ok(count)
+use crate::{
+    parse_input,
+    runner::Runner,
+    utils::{
+        equation::{Operator, Solver},
+        numbers::big_int::BigInt,
+        parallel::par_map,
+    },
+    Error,
+};
+
+/// Total of the results which can be made from their inputs with + and *
+fn problem07_part1(input: &Input) -> Result<BigInt, Error> {
+    Ok(calibration_total(
+        input,
+        &Solver::new(&[Operator::Add, Operator::Multiply]),
+    ))
 }
 
-fn problem07_part2(input: &Input) -> Result<u64, Error> {
-    let mut count = 0;
+/// As part 1 with concatenation allowed too
+fn problem07_part2(input: &Input) -> Result<BigInt, Error> {
+    Ok(calibration_total(
+        input,
+        &Solver::new(&[Operator::Add, Operator::Multiply, Operator::Concat]),
+    ))
+}
 
-    for equation in &input.equations {
-        if equation.solvable_three_operands() {
-            count += equation.result;
+/// Undoing addition, multiplication and concatenation never gives a bigger value, so the
+/// backwards search stays within each result, but the sum of the results can still
+/// outgrow a u64. Each equation is a depth first search needing memory for a single
+/// path, and they're independent so they're spread across threads
+fn calibration_total(input: &Input, solver: &Solver) -> BigInt {
+    par_map(&input.equations, |equation| {
+        match solver.solvable(equation.result, &equation.inputs) {
+            true => BigInt::from(equation.result),
+            false => BigInt::zero(),
         }
-    }
-
-    Ok(count)
+    })
+    .into_iter()
+    .sum()
 }
 
 struct Equation {
@@ -42,60 +57,6 @@ impl Display for Equation {
     }
 }
 
-impl Equation {
-    fn solvable(&self) -> bool {
-        let mut previous_values: Vec<u64> = vec![self.inputs[0]];
-
-        // Making an assumption that all input vectors have at least 2 values
-        for input_val in self.inputs[1..].iter() {
-            let mut next_values: Vec<u64> = Vec::with_capacity(previous_values.len() * 2);
-
-            for val in previous_values {
-                match val.checked_add(*input_val) {
-                    Some(v) => next_values.push(v),
-                    None => (),
-                }
-                match val.checked_mul(*input_val) {
-                    Some(v) => next_values.push(v),
-                    None => (),
-                }
-            }
-
-            previous_values = next_values;
-        }
-
-        previous_values.contains(&self.result)
-    }
-
-    fn solvable_three_operands(&self) -> bool {
-        let mut previous_values: Vec<u64> = vec![self.inputs[0]];
-
-        // Making an assumption that all input vectors have at least 2 values
-        for input_val in self.inputs[1..].iter() {
-            let mut next_values: Vec<u64> = Vec::with_capacity(previous_values.len() * 2);
-
-            for val in previous_values {
-                match val.checked_add(*input_val) {
-                    Some(v) => next_values.push(v),
-                    None => (),
-                }
-                match val.checked_mul(*input_val) {
-                    Some(v) => next_values.push(v),
-                    None => (),
-                }
-                match format!("{}{}", val, input_val).parse::<u64>() {
-                    Ok(v) => next_values.push(v),
-                    Err(_) => (),
-                }
-            }
-
-            previous_values = next_values;
-        }
-
-        previous_values.contains(&self.result)
-    }
-}
-
 struct Input {
     equations: Vec<Equation>,
 }
@@ -118,15 +79,10 @@ fn get_equations_from_input(lines: Vec<String>) -> Result<Input, Error> {
     Ok(Input { equations })
 }
 
-pub fn problem07() -> Result<(), Error> {
+pub fn problem07(runner: &Runner) -> Result<String, Error> {
     let input = parse_input("input/problem_07.txt")?;
 
-    let mut parsed_input = get_equations_from_input(input)?;
-
-    let solution_one = problem07_part1(&parsed_input)?;
-    println!("Problem 07 Part 1: {solution_one}");
-    let solution_two = problem07_part2(&mut parsed_input)?;
-    println!("Problem 07 Part 2: {solution_two}");
+    let parsed_input = get_equations_from_input(input)?;
 
-    Ok(())
+    runner.parts(7, &parsed_input, problem07_part1, problem07_part2)
 }
@@ -2,8 +2,8 @@ use std::collections::HashMap;
 
 use crate::{
     parse_input,
+    runner::Runner,
     structures::{coordinate::Coordinate, grid::Grid},
-    utils::numbers::{CheckedAdd, CheckedSub},
     Error,
 };
 
@@ -51,32 +51,14 @@ fn get_anti_nodes_for_two_coordinates(
     b: Coordinate<i64>,
     grid: &Grid<i64, char>,
 ) -> Vec<Coordinate<i64>> {
-    let mut anti_nodes = Vec::with_capacity(2);
-
     // This is the slope moving from a to b
-    let delta = a.get_slope_to(&b).expect("Valid Slope");
-
-    // The anti-node near b would be adding the slope to b
-    match b.checked_add(delta) {
-        Some(anti_b) => {
-            if grid.valid_index(anti_b.row, anti_b.column) {
-                anti_nodes.push(anti_b);
-            }
-        }
-        None => (),
-    }
+    let delta = b - a;
 
-    // The anti-node near a would be subtracting the slope from a
-    match a.checked_sub(delta) {
-        Some(anti_a) => {
-            if grid.valid_index(anti_a.row, anti_a.column) {
-                anti_nodes.push(anti_a);
-            }
-        }
-        None => (),
-    }
-
-    anti_nodes
+    // The anti-node near b is one slope past b and the anti-node near a is one slope before a
+    [b + delta, a - delta]
+        .into_iter()
+        .filter(|anti_node| grid.valid_index(anti_node.row, anti_node.column))
+        .collect()
 }
 
 fn get_inline_anti_nodes(
@@ -86,21 +68,12 @@ fn get_inline_anti_nodes(
 ) -> Vec<Coordinate<i64>> {
     let mut anti_nodes = Vec::new();
 
-    let delta = a.get_slope_to(&b).expect("Valid Slope");
+    // Step by the smallest lattice step so no point on the line is skipped
+    let delta = a.get_primitive_slope_to(&b).expect("Antennas are distinct");
 
-    // Add to b until we're outside the grid
-    let mut current_pos = b;
-    while grid.valid_index(current_pos.row, current_pos.column) {
-        anti_nodes.push(current_pos);
-        current_pos = current_pos.checked_add(delta).expect("No overflow");
-    }
-
-    // Sub from a until we're outside the grid
-    current_pos = a;
-    while grid.valid_index(current_pos.row, current_pos.column) {
-        anti_nodes.push(current_pos);
-        current_pos = current_pos.checked_sub(delta).expect("No underflow");
-    }
+    // Walk from a through b and on until we're outside the grid, then the other way from a
+    anti_nodes.extend(grid.ray(a, delta));
+    anti_nodes.extend(grid.ray(a - delta, -delta));
 
     anti_nodes
 }
@@ -144,15 +117,31 @@ fn get_unique_antenna_coordinates(lines: Vec<String>) -> Result<Input, Error> {
     })
 }
 
-pub fn problem08() -> Result<(), Error> {
+pub fn problem08(runner: &Runner) -> Result<String, Error> {
     let input = parse_input("input/problem_08.txt")?;
 
-    let mut parsed_input = get_unique_antenna_coordinates(input)?;
+    let parsed_input = get_unique_antenna_coordinates(input)?;
 
-    let solution_one = problem08_part1(&parsed_input)?;
-    println!("Problem 08 Part 1: {solution_one}");
-    let solution_two = problem08_part2(&mut parsed_input)?;
-    println!("Problem 08 Part 2: {solution_two}");
+    runner.parts(8, &parsed_input, problem08_part1, problem08_part2)
+}
 
-    Ok(())
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn inline_anti_nodes_include_points_between_antennas() {
+        let grid: Grid<i64, char> = Grid::new(5, 5);
+        let mut anti_nodes =
+            get_inline_anti_nodes(Coordinate::new(0, 0), Coordinate::new(2, 4), &grid);
+        anti_nodes.sort_by_key(|c| (c.row, c.column));
+        assert_eq!(
+            vec![
+                Coordinate::new(0, 0),
+                Coordinate::new(1, 2),
+                Coordinate::new(2, 4)
+            ],
+            anti_nodes
+        );
+    }
 }
@@ -0,0 +1,129 @@
+use std::{fmt::Display, thread};
+
+use crate::Error;
+
+/// A day's entry point. Returns its output lines rather than printing them so days
+/// running on separate threads can still be printed in order
+pub type Day = fn(&Runner) -> Result<String, Error>;
+
+/// How the days and their parts get run
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Runner {
+    /// Run independent days, and the two parts of a day, on their own threads
+    pub parallel: bool,
+}
+
+impl Runner {
+    /// Reads the command line flags. Only `--parallel` is understood
+    pub fn from_args<I>(args: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        Runner {
+            parallel: args.into_iter().any(|arg| arg == "--parallel"),
+        }
+    }
+
+    /// Run both parts of a day against the same input and format the output lines.
+    /// The parts only share the input immutably so they can run at the same time
+    pub fn parts<I, A, B, P1, P2>(
+        &self,
+        day: u8,
+        input: &I,
+        part1: P1,
+        part2: P2,
+    ) -> Result<String, Error>
+    where
+        I: Sync,
+        A: Display + Send,
+        B: Display + Send,
+        P1: FnOnce(&I) -> Result<A, Error> + Send,
+        P2: FnOnce(&I) -> Result<B, Error> + Send,
+    {
+        let (solution_one, solution_two) = if self.parallel {
+            thread::scope(|s| {
+                let one = s.spawn(|| part1(input));
+                let two = part2(input);
+                (one.join().expect("Part 1 panicked"), two)
+            })
+        } else {
+            (part1(input), part2(input))
+        };
+
+        Ok(format_parts(day, solution_one?, solution_two?))
+    }
+
+    /// Run every day, returning each result in the order given
+    pub fn days(&self, days: &[Day]) -> Vec<Result<String, Error>> {
+        if !self.parallel {
+            return days.iter().map(|day| day(self)).collect();
+        }
+
+        thread::scope(|s| {
+            let handles: Vec<_> = days.iter().map(|day| s.spawn(|| day(self))).collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("Day panicked"))
+                .collect()
+        })
+    }
+}
+
+/// The output lines for a day with both parts solved
+pub fn format_parts<A, B>(day: u8, solution_one: A, solution_two: B) -> String
+where
+    A: Display,
+    B: Display,
+{
+    format!("Problem {day:02} Part 1: {solution_one}\nProblem {day:02} Part 2: {solution_two}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn add_one(input: &i32) -> Result<i32, Error> {
+        Ok(input + 1)
+    }
+
+    fn fail(_: &i32) -> Result<i32, Error> {
+        Err(Error::PreprocessError("Nope".to_string()))
+    }
+
+    #[test]
+    fn parts_output_is_the_same_either_way() -> Result<(), Error> {
+        for parallel in [false, true] {
+            let runner = Runner { parallel };
+            assert_eq!(
+                "Problem 03 Part 1: 2\nProblem 03 Part 2: 42",
+                runner.parts(3, &1, add_one, |_| Ok::<_, Error>("42"))?
+            );
+            assert!(runner.parts(3, &1, add_one, fail).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn days_keep_their_order() {
+        let days: [Day; 3] = [
+            |_| Ok("one".to_string()),
+            |_| {
+                thread::sleep(std::time::Duration::from_millis(20));
+                Ok("two".to_string())
+            },
+            |_| Ok("three".to_string()),
+        ];
+        let outputs: Vec<String> = Runner { parallel: true }
+            .days(&days)
+            .into_iter()
+            .map(|output| output.unwrap())
+            .collect();
+        assert_eq!(vec!["one", "two", "three"], outputs);
+    }
+
+    #[test]
+    fn flag_from_args() {
+        assert!(Runner::from_args(["aoc".to_string(), "--parallel".to_string()]).parallel);
+        assert!(!Runner::from_args(["aoc".to_string()]).parallel);
+    }
+}
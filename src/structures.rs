@@ -1,2 +1,9 @@
 pub mod coordinate;
+pub mod coordinate3;
 pub mod grid;
+pub mod grid3;
+pub mod hex;
+pub mod ordering;
+pub mod polygon;
+pub mod rect;
+pub mod walker;
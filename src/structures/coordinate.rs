@@ -1,6 +1,23 @@
+use std::{
+    cmp::Ordering,
+    ops::{Add, Mul, Neg, Sub},
+};
+
 use strum_macros::{Display, EnumString, VariantArray};
+use thiserror::Error;
+
+use crate::utils::numbers::{gcd, CheckedAdd, CheckedSub, Integer};
 
-use crate::utils::numbers::{CheckedAdd, CheckedDecrement, CheckedIncrement, CheckedSub};
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    /// The character doesn't describe a direction
+    #[error("'{0}' is not a direction")]
+    InvalidDirection(char),
+
+    /// A diagonal was given where only North, East, South or West make sense
+    #[error("{0} is not a cardinal direction")]
+    NotCardinal(Direction),
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, VariantArray)]
 pub enum Direction {
@@ -15,6 +32,8 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// Turn clockwise by 90 degrees. Diagonals stay diagonal, use `CardinalDirection`
+    /// when only the four compass points are valid.
     pub fn rotate_90(&self) -> Direction {
         match self {
             Direction::North => Direction::East,
@@ -27,6 +46,156 @@ impl Direction {
             Direction::NorthWest => Direction::NorthEast,
         }
     }
+
+    /// Turn clockwise by 45 degrees
+    pub fn rotate_45(&self) -> Direction {
+        match self {
+            Direction::North => Direction::NorthEast,
+            Direction::NorthEast => Direction::East,
+            Direction::East => Direction::SouthEast,
+            Direction::SouthEast => Direction::South,
+            Direction::South => Direction::SouthWest,
+            Direction::SouthWest => Direction::West,
+            Direction::West => Direction::NorthWest,
+            Direction::NorthWest => Direction::North,
+        }
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        self.rotate_90()
+    }
+
+    pub fn turn_left(&self) -> Direction {
+        self.reverse().rotate_90()
+    }
+
+    pub fn reverse(&self) -> Direction {
+        self.rotate_90().rotate_90()
+    }
+
+    /// The single step taken when traversing in this direction
+    pub fn to_delta(self) -> Coordinate<isize> {
+        Coordinate::from(self)
+    }
+}
+
+/// The four compass points. Useful whenever diagonal movement isn't allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, VariantArray)]
+pub enum CardinalDirection {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl CardinalDirection {
+    pub fn turn_right(&self) -> CardinalDirection {
+        match self {
+            CardinalDirection::North => CardinalDirection::East,
+            CardinalDirection::East => CardinalDirection::South,
+            CardinalDirection::South => CardinalDirection::West,
+            CardinalDirection::West => CardinalDirection::North,
+        }
+    }
+
+    pub fn turn_left(&self) -> CardinalDirection {
+        match self {
+            CardinalDirection::North => CardinalDirection::West,
+            CardinalDirection::East => CardinalDirection::North,
+            CardinalDirection::South => CardinalDirection::East,
+            CardinalDirection::West => CardinalDirection::South,
+        }
+    }
+
+    pub fn reverse(&self) -> CardinalDirection {
+        self.turn_right().turn_right()
+    }
+
+    /// The single step taken when traversing in this direction
+    pub fn to_delta(self) -> Coordinate<isize> {
+        Direction::from(self).to_delta()
+    }
+
+    /// Only accepts the arrow characters used on puzzle maps (`^>v<`)
+    pub fn from_arrow(arrow: char) -> Result<CardinalDirection, Error> {
+        match arrow {
+            '^' => Ok(CardinalDirection::North),
+            '>' => Ok(CardinalDirection::East),
+            'v' => Ok(CardinalDirection::South),
+            '<' => Ok(CardinalDirection::West),
+            c => Err(Error::InvalidDirection(c)),
+        }
+    }
+
+    /// The arrow character used on puzzle maps
+    pub fn to_arrow(self) -> char {
+        match self {
+            CardinalDirection::North => '^',
+            CardinalDirection::East => '>',
+            CardinalDirection::South => 'v',
+            CardinalDirection::West => '<',
+        }
+    }
+
+    /// The compass letter, one of `NESW`
+    pub fn to_compass_letter(self) -> char {
+        match self {
+            CardinalDirection::North => 'N',
+            CardinalDirection::East => 'E',
+            CardinalDirection::South => 'S',
+            CardinalDirection::West => 'W',
+        }
+    }
+
+    /// The up/down/left/right letter, one of `UDLR`
+    pub fn to_udlr_letter(self) -> char {
+        match self {
+            CardinalDirection::North => 'U',
+            CardinalDirection::East => 'R',
+            CardinalDirection::South => 'D',
+            CardinalDirection::West => 'L',
+        }
+    }
+}
+
+impl From<CardinalDirection> for Direction {
+    fn from(value: CardinalDirection) -> Self {
+        match value {
+            CardinalDirection::North => Direction::North,
+            CardinalDirection::East => Direction::East,
+            CardinalDirection::South => Direction::South,
+            CardinalDirection::West => Direction::West,
+        }
+    }
+}
+
+impl TryFrom<Direction> for CardinalDirection {
+    type Error = Error;
+
+    fn try_from(value: Direction) -> Result<Self, Self::Error> {
+        match value {
+            Direction::North => Ok(CardinalDirection::North),
+            Direction::East => Ok(CardinalDirection::East),
+            Direction::South => Ok(CardinalDirection::South),
+            Direction::West => Ok(CardinalDirection::West),
+            diagonal => Err(Error::NotCardinal(diagonal)),
+        }
+    }
+}
+
+/// Accepts arrows (`^>v<`), up/down/left/right (`UDLR`) and compass letters (`NESW`)
+impl TryFrom<char> for CardinalDirection {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'U' | 'N' => Ok(CardinalDirection::North),
+            'R' | 'E' => Ok(CardinalDirection::East),
+            'D' | 'S' => Ok(CardinalDirection::South),
+            'L' | 'W' => Ok(CardinalDirection::West),
+            c => CardinalDirection::from_arrow(c),
+        }
+    }
 }
 
 #[derive(Debug, Hash, Copy, Clone)]
@@ -90,9 +259,108 @@ impl<T> Coordinate<T> {
     }
 }
 
+impl<T> Add for Coordinate<T>
+where
+    T: Add<Output = T>,
+{
+    type Output = Coordinate<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Coordinate::new(self.row + rhs.row, self.column + rhs.column)
+    }
+}
+
+impl<T> Sub for Coordinate<T>
+where
+    T: Sub<Output = T>,
+{
+    type Output = Coordinate<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Coordinate::new(self.row - rhs.row, self.column - rhs.column)
+    }
+}
+
+impl<T> Neg for Coordinate<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Coordinate<T>;
+
+    fn neg(self) -> Self::Output {
+        Coordinate::new(-self.row, -self.column)
+    }
+}
+
+/// Scale both components by the same value
+impl<T> Mul<T> for Coordinate<T>
+where
+    T: Mul<Output = T> + Copy,
+{
+    type Output = Coordinate<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Coordinate::new(self.row * rhs, self.column * rhs)
+    }
+}
+
 impl<T> Coordinate<T>
 where
-    T: CheckedSub + Copy,
+    T: Integer,
+{
+    /// Scale both components by `scalar`. None if either component overflows
+    pub fn checked_mul(&self, scalar: T) -> Option<Coordinate<T>> {
+        let row = self.row.checked_mul(scalar)?;
+        let column = self.column.checked_mul(scalar)?;
+        Some(Coordinate::new(row, column))
+    }
+
+    /// Treating both coordinates as vectors from the origin. Panics on overflow
+    pub fn dot(&self, other: &Coordinate<T>) -> T {
+        self.row * other.row + self.column * other.column
+    }
+
+    /// `dot`, but None if it overflows
+    pub fn checked_dot(&self, other: &Coordinate<T>) -> Option<T> {
+        self.row
+            .checked_mul(other.row)?
+            .checked_add(self.column.checked_mul(other.column)?)
+    }
+
+    /// The z component of the 3D cross product of two vectors lying flat on the grid.
+    /// Zero when the vectors are parallel. Panics on overflow, which for unsigned
+    /// types includes any negative result
+    pub fn cross(&self, other: &Coordinate<T>) -> T {
+        self.row * other.column - self.column * other.row
+    }
+
+    /// `cross`, but None if it overflows or is negative for an unsigned type
+    pub fn checked_cross(&self, other: &Coordinate<T>) -> Option<T> {
+        self.row
+            .checked_mul(other.column)?
+            .checked_sub(self.column.checked_mul(other.row)?)
+    }
+}
+
+/// The single step taken when traversing in a direction
+impl From<Direction> for Coordinate<isize> {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::North => Coordinate::new(-1, 0),
+            Direction::NorthEast => Coordinate::new(-1, 1),
+            Direction::East => Coordinate::new(0, 1),
+            Direction::SouthEast => Coordinate::new(1, 1),
+            Direction::South => Coordinate::new(1, 0),
+            Direction::SouthWest => Coordinate::new(1, -1),
+            Direction::West => Coordinate::new(0, -1),
+            Direction::NorthWest => Coordinate::new(-1, -1),
+        }
+    }
+}
+
+impl<T> Coordinate<T>
+where
+    T: Integer,
 {
     pub fn get_slope_to(&self, other: &Coordinate<T>) -> Option<Coordinate<T>> {
         let row_delta = match other.row.checked_sub(self.row) {
@@ -107,12 +375,164 @@ where
         // This is the slope moving from a to b
         Some(Coordinate::new(row_delta, column_delta))
     }
-}
 
-impl<T> Coordinate<T>
-where
-    T: CheckedIncrement + CheckedDecrement + Copy,
-{
+    /// The slope to `other` reduced to the smallest step which still lands on lattice points.
+    /// Every lattice point on the line between the two coordinates is a multiple of this step.
+    /// None if the coordinates are the same or the math overflows.
+    pub fn get_primitive_slope_to(&self, other: &Coordinate<T>) -> Option<Coordinate<T>> {
+        let slope = self.get_slope_to(other)?;
+
+        let row_distance = self
+            .row
+            .max(other.row)
+            .checked_sub(self.row.min(other.row))?;
+        let column_distance = self
+            .column
+            .max(other.column)
+            .checked_sub(self.column.min(other.column))?;
+
+        let divisor = gcd(row_distance, column_distance);
+        if divisor == T::ZERO {
+            return None;
+        }
+
+        Some(Coordinate::new(slope.row / divisor, slope.column / divisor))
+    }
+
+    /// Number of orthogonal steps between the two coordinates. None if it overflows
+    pub fn manhattan_distance(&self, other: &Coordinate<T>) -> Option<T> {
+        let (row_distance, column_distance) = self.axis_distances(other)?;
+        row_distance.checked_add(column_distance)
+    }
+
+    /// Number of steps between the two coordinates when diagonal moves are allowed.
+    /// None if it overflows
+    pub fn chebyshev_distance(&self, other: &Coordinate<T>) -> Option<T> {
+        let (row_distance, column_distance) = self.axis_distances(other)?;
+        Some(row_distance.max(column_distance))
+    }
+
+    /// Squared straight line distance. Kept squared so it stays an exact integer. None
+    /// if it overflows
+    pub fn squared_euclidean_distance(&self, other: &Coordinate<T>) -> Option<T> {
+        let (row_distance, column_distance) = self.axis_distances(other)?;
+        row_distance
+            .checked_mul(row_distance)?
+            .checked_add(column_distance.checked_mul(column_distance)?)
+    }
+
+    /// How far apart the coordinates are along each axis. Largest minus smallest so
+    /// unsigned types never go below zero
+    fn axis_distances(&self, other: &Coordinate<T>) -> Option<(T, T)> {
+        Some((
+            self.row
+                .max(other.row)
+                .checked_sub(self.row.min(other.row))?,
+            self.column
+                .max(other.column)
+                .checked_sub(self.column.min(other.column))?,
+        ))
+    }
+
+    /// True when all three coordinates sit on a single line. None when the vectors
+    /// between them don't fit in an i128
+    pub fn is_collinear(&self, b: &Coordinate<T>, c: &Coordinate<T>) -> Option<bool> {
+        let cross = self
+            .signed_delta_to(b)?
+            .checked_cross(&self.signed_delta_to(c)?)?;
+        Some(cross == 0)
+    }
+
+    /// Order `a` and `b` by their angle around this coordinate, sweeping clockwise
+    /// starting from North. Coordinates at the same angle are ordered nearest first.
+    /// None when the vectors to them don't fit in an i128
+    pub fn compare_angle(&self, a: &Coordinate<T>, b: &Coordinate<T>) -> Option<Ordering> {
+        let a_delta = self.signed_delta_to(a)?;
+        let b_delta = self.signed_delta_to(b)?;
+
+        let a_half = Self::angle_half(&a_delta);
+        let b_half = Self::angle_half(&b_delta);
+        if a_half != b_half {
+            return Some(a_half.cmp(&b_half));
+        }
+
+        // Rows grow downwards so a clockwise turn from a to b has a negative cross product
+        match a_delta.checked_cross(&b_delta)?.cmp(&0) {
+            Ordering::Less => Some(Ordering::Less),
+            Ordering::Greater => Some(Ordering::Greater),
+            Ordering::Equal => Some(
+                a_delta
+                    .checked_dot(&a_delta)?
+                    .cmp(&b_delta.checked_dot(&b_delta)?),
+            ),
+        }
+    }
+
+    /// Every lattice point on the line from this coordinate to `other` using
+    /// Bresenham's algorithm. Both ends are included. None when the vector between
+    /// them doesn't fit in an i128
+    pub fn rasterize_line_to(&self, other: &Coordinate<T>) -> Option<Vec<Coordinate<T>>> {
+        let delta = self.signed_delta_to(other)?;
+        let column_distance = delta.column.checked_abs()?;
+        let row_distance = -delta.row.checked_abs()?;
+
+        let column_step = match delta.column >= 0 {
+            true => Direction::East,
+            false => Direction::West,
+        };
+        let row_step = match delta.row >= 0 {
+            true => Direction::South,
+            false => Direction::North,
+        };
+
+        let mut line = Vec::new();
+        let mut current = *self;
+        let mut error = column_distance + row_distance;
+        loop {
+            line.push(current);
+            if current == *other {
+                break;
+            }
+
+            let doubled_error = error.checked_mul(2)?;
+            // Points between the ends can't overflow
+            if doubled_error >= row_distance {
+                error = error.checked_add(row_distance)?;
+                current = current.traverse(column_step).expect("Between the ends");
+            }
+            if doubled_error <= column_distance {
+                error = error.checked_add(column_distance)?;
+                current = current.traverse(row_step).expect("Between the ends");
+            }
+        }
+
+        Some(line)
+    }
+
+    /// 0 for North up to (but not including) South when sweeping clockwise. 1 otherwise
+    fn angle_half(delta: &Coordinate<i128>) -> u8 {
+        if delta.column > 0 || (delta.column == 0 && delta.row <= 0) {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// The slope to `other` widened so it can go negative even for unsigned coordinates.
+    /// None when the difference doesn't fit in an i128, which is only possible for u128
+    /// and i128
+    fn signed_delta_to(&self, other: &Coordinate<T>) -> Option<Coordinate<i128>> {
+        // Subtract the smaller from the larger first so huge u128 values still work
+        let delta = |from: T, to: T| match to >= from {
+            true => (to - from).to_i128(),
+            false => (from - to).to_i128()?.checked_neg(),
+        };
+        Some(Coordinate::new(
+            delta(self.row, other.row)?,
+            delta(self.column, other.column)?,
+        ))
+    }
+
     pub fn traverse(&self, direction: Direction) -> Option<Coordinate<T>> {
         match direction {
             Direction::North => {
@@ -195,6 +615,7 @@ where
     }
 }
 
+#[cfg(test)]
 mod test {
     use strum::VariantArray;
 
@@ -497,4 +918,277 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn vector_operators() {
+        let a = Coordinate::new(1_i64, 2);
+        let b = Coordinate::new(3_i64, -4);
+        assert_eq!(Coordinate::new(4, -2), a + b);
+        assert_eq!(Coordinate::new(-2, 6), a - b);
+        assert_eq!(Coordinate::new(-1, -2), -a);
+        assert_eq!(Coordinate::new(3, 6), a * 3);
+        assert_eq!(-5, a.dot(&b));
+        assert_eq!(-10, a.cross(&b));
+        assert_eq!(0, a.cross(&(a * 4)));
+        assert_eq!(Some(-5), a.checked_dot(&b));
+        assert_eq!(Some(-10), a.checked_cross(&b));
+    }
+
+    #[test]
+    fn checked_vector_products_on_unsigned() {
+        let a = Coordinate::new(1_usize, 2);
+        let b = Coordinate::new(3_usize, 4);
+        assert_eq!(Some(11), a.checked_dot(&b));
+        assert_eq!(Some(2), b.checked_cross(&a));
+        // Negative cross products can't be represented
+        assert_eq!(None, a.checked_cross(&b));
+
+        let big = Coordinate::new(16_u8, 0);
+        assert_eq!(None, big.checked_dot(&big));
+        assert_eq!(Some(0), big.checked_cross(&big));
+    }
+
+    #[test]
+    fn checked_scalar_multiplication() {
+        assert_eq!(
+            Some(Coordinate::new(4_u32, 6)),
+            Coordinate::new(2_u32, 3).checked_mul(2)
+        );
+        assert_eq!(None, Coordinate::new(1_u32, u32::MAX).checked_mul(2));
+    }
+
+    #[test]
+    fn direction_delta_matches_traverse() {
+        let center = Coordinate::new(5_isize, 5);
+        for direction in Direction::VARIANTS {
+            assert_eq!(
+                center.traverse(*direction),
+                Some(center + Coordinate::from(*direction))
+            );
+        }
+    }
+
+    #[test]
+    fn distance_metrics() {
+        let a = Coordinate::new(1_usize, 7);
+        let b = Coordinate::new(4_usize, 3);
+        assert_eq!(Some(7), a.manhattan_distance(&b));
+        assert_eq!(Some(4), a.chebyshev_distance(&b));
+        assert_eq!(Some(25), a.squared_euclidean_distance(&b));
+        assert_eq!(Some(0), a.manhattan_distance(&a));
+    }
+
+    #[test]
+    fn distance_metrics_overflow() {
+        let a = Coordinate::new(0_u8, 0);
+        let b = Coordinate::new(20_u8, 3);
+        assert_eq!(Some(23), a.manhattan_distance(&b));
+        assert_eq!(Some(20), a.chebyshev_distance(&b));
+        // 400 doesn't fit in a u8
+        assert_eq!(None, a.squared_euclidean_distance(&b));
+        assert_eq!(None, a.manhattan_distance(&Coordinate::new(200, 200)));
+
+        // The delta itself can overflow a signed type
+        let far = Coordinate::new(i32::MAX, 0);
+        assert_eq!(None, Coordinate::new(i32::MIN, 0).chebyshev_distance(&far));
+    }
+
+    #[test]
+    fn primitive_slope() {
+        let a = Coordinate::new(0_i64, 0);
+        assert_eq!(
+            Some(Coordinate::new(2, -3)),
+            a.get_primitive_slope_to(&Coordinate::new(4, -6))
+        );
+        assert_eq!(
+            Some(Coordinate::new(0, 1)),
+            a.get_primitive_slope_to(&Coordinate::new(0, 5))
+        );
+        assert_eq!(
+            Some(Coordinate::new(-1, 0)),
+            a.get_primitive_slope_to(&Coordinate::new(-3, 0))
+        );
+        assert_eq!(None, a.get_primitive_slope_to(&a));
+    }
+
+    #[test]
+    fn collinear() {
+        let a = Coordinate::new(0_i32, 0);
+        assert_eq!(
+            Some(true),
+            a.is_collinear(&Coordinate::new(1, 2), &Coordinate::new(3, 6))
+        );
+        assert_eq!(
+            Some(true),
+            a.is_collinear(&Coordinate::new(1, 2), &Coordinate::new(-2, -4))
+        );
+        assert_eq!(
+            Some(false),
+            a.is_collinear(&Coordinate::new(1, 2), &Coordinate::new(2, 3))
+        );
+    }
+
+    #[test]
+    fn angle_ordering_sweeps_clockwise_from_north() {
+        let center = Coordinate::new(0_i32, 0);
+        let mut points = vec![
+            Coordinate::new(0, -1),
+            Coordinate::new(1, 1),
+            Coordinate::new(-2, 0),
+            Coordinate::new(1, 0),
+            Coordinate::new(-1, 0),
+            Coordinate::new(-1, -1),
+            Coordinate::new(0, 1),
+            Coordinate::new(-1, 1),
+        ];
+        points.sort_by(|a, b| center.compare_angle(a, b).unwrap());
+        assert_eq!(
+            vec![
+                Coordinate::new(-1, 0),
+                Coordinate::new(-2, 0),
+                Coordinate::new(-1, 1),
+                Coordinate::new(0, 1),
+                Coordinate::new(1, 1),
+                Coordinate::new(1, 0),
+                Coordinate::new(0, -1),
+                Coordinate::new(-1, -1),
+            ],
+            points
+        );
+    }
+
+    #[test]
+    fn rasterize_lines() {
+        let a = Coordinate::new(0_i32, 0);
+        assert_eq!(Some(vec![a]), a.rasterize_line_to(&a));
+        assert_eq!(
+            Some(vec![
+                Coordinate::new(0, 0),
+                Coordinate::new(0, 1),
+                Coordinate::new(0, 2)
+            ]),
+            a.rasterize_line_to(&Coordinate::new(0, 2))
+        );
+        assert_eq!(
+            Some(vec![
+                Coordinate::new(0, 0),
+                Coordinate::new(-1, -1),
+                Coordinate::new(-2, -2)
+            ]),
+            a.rasterize_line_to(&Coordinate::new(-2, -2))
+        );
+        assert_eq!(
+            Some(vec![
+                Coordinate::new(0, 0),
+                Coordinate::new(0, 1),
+                Coordinate::new(1, 2),
+                Coordinate::new(1, 3)
+            ]),
+            a.rasterize_line_to(&Coordinate::new(1, 3))
+        );
+
+        // Lines are symmetric in the points they cover
+        let b = Coordinate::new(7, -3);
+        let mut forward = a.rasterize_line_to(&b).unwrap();
+        let mut backward = b.rasterize_line_to(&a).unwrap();
+        assert_eq!(8, forward.len());
+        forward.sort_by_key(|c| (c.row, c.column));
+        backward.sort_by_key(|c| (c.row, c.column));
+        assert_eq!(forward, backward);
+    }
+
+    #[test]
+    fn geometry_beyond_i128() {
+        let a = Coordinate::new(0_u128, 0);
+        let far = Coordinate::new(u128::MAX, 1);
+        assert_eq!(None, a.is_collinear(&far, &Coordinate::new(1, 1)));
+        assert_eq!(None, a.compare_angle(&far, &Coordinate::new(1, 1)));
+        assert_eq!(None, a.rasterize_line_to(&far));
+
+        // Big values are fine while the vectors between them fit
+        let b = Coordinate::new(u128::MAX - 2, u128::MAX);
+        assert_eq!(
+            Some(true),
+            b.is_collinear(
+                &Coordinate::new(u128::MAX - 1, u128::MAX),
+                &Coordinate::new(u128::MAX, u128::MAX)
+            )
+        );
+    }
+
+    #[test]
+    fn direction_turns() {
+        for direction in Direction::VARIANTS {
+            assert_eq!(direction.rotate_90(), direction.rotate_45().rotate_45());
+            assert_eq!(*direction, direction.turn_left().turn_right());
+            assert_eq!(*direction, direction.reverse().reverse());
+            assert_eq!(-direction.to_delta(), direction.reverse().to_delta());
+        }
+        assert_eq!(Direction::West, Direction::North.turn_left());
+        assert_eq!(Direction::SouthWest, Direction::NorthEast.reverse());
+    }
+
+    #[test]
+    fn cardinal_directions() {
+        for direction in CardinalDirection::VARIANTS {
+            assert_eq!(*direction, direction.turn_left().turn_right());
+            assert_eq!(
+                Direction::from(direction.turn_right()),
+                Direction::from(*direction).turn_right()
+            );
+            assert_eq!(
+                Ok(*direction),
+                CardinalDirection::try_from(direction.to_arrow())
+            );
+            assert_eq!(
+                Ok(*direction),
+                CardinalDirection::from_arrow(direction.to_arrow())
+            );
+            assert_eq!(
+                Ok(*direction),
+                CardinalDirection::try_from(Direction::from(*direction))
+            );
+            assert_eq!(direction.to_delta(), Direction::from(*direction).to_delta());
+        }
+        assert_eq!(
+            Err(Error::NotCardinal(Direction::SouthEast)),
+            CardinalDirection::try_from(Direction::SouthEast)
+        );
+    }
+
+    #[test]
+    fn cardinal_direction_letters_round_trip() {
+        for direction in CardinalDirection::VARIANTS {
+            assert_eq!(
+                Ok(*direction),
+                CardinalDirection::try_from(direction.to_compass_letter())
+            );
+            assert_eq!(
+                Ok(*direction),
+                CardinalDirection::try_from(direction.to_udlr_letter())
+            );
+        }
+        assert_eq!('W', CardinalDirection::West.to_compass_letter());
+        assert_eq!('L', CardinalDirection::West.to_udlr_letter());
+    }
+
+    #[test]
+    fn cardinal_direction_from_letters() {
+        assert_eq!(Ok(CardinalDirection::North), 'U'.try_into());
+        assert_eq!(Ok(CardinalDirection::South), 'D'.try_into());
+        assert_eq!(Ok(CardinalDirection::West), 'L'.try_into());
+        assert_eq!(Ok(CardinalDirection::East), 'R'.try_into());
+        assert_eq!(Ok(CardinalDirection::North), 'N'.try_into());
+        assert_eq!(Ok(CardinalDirection::East), 'E'.try_into());
+        assert_eq!(Ok(CardinalDirection::South), 'S'.try_into());
+        assert_eq!(Ok(CardinalDirection::West), 'W'.try_into());
+        assert_eq!(
+            Err::<CardinalDirection, Error>(Error::InvalidDirection('x')),
+            'x'.try_into()
+        );
+        assert_eq!(
+            Err(Error::InvalidDirection('N')),
+            CardinalDirection::from_arrow('N')
+        );
+    }
 }
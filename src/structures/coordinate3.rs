@@ -0,0 +1,268 @@
+use strum_macros::{Display, EnumString, VariantArray};
+
+use crate::utils::numbers::{CheckedAdd, CheckedSub, Integer};
+
+/// The six faces of a voxel. North/South and East/West follow the same
+/// convention as the 2D `Direction`, Up and Down move along the z axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, VariantArray)]
+pub enum Direction3 {
+    North,
+    East,
+    South,
+    West,
+    Up,
+    Down,
+}
+
+impl Direction3 {
+    /// The direction facing the opposite way
+    pub fn reverse(&self) -> Direction3 {
+        match self {
+            Direction3::North => Direction3::South,
+            Direction3::East => Direction3::West,
+            Direction3::South => Direction3::North,
+            Direction3::West => Direction3::East,
+            Direction3::Up => Direction3::Down,
+            Direction3::Down => Direction3::Up,
+        }
+    }
+}
+
+#[derive(Debug, Hash, Copy, Clone, PartialEq, Eq)]
+pub struct Coordinate3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Coordinate3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> CheckedAdd for Coordinate3<T>
+where
+    T: CheckedAdd,
+{
+    fn checked_add(&self, rhs: Self) -> Option<Self> {
+        let x = self.x.checked_add(rhs.x)?;
+        let y = self.y.checked_add(rhs.y)?;
+        let z = self.z.checked_add(rhs.z)?;
+        Some(Coordinate3::new(x, y, z))
+    }
+}
+
+impl<T> CheckedSub for Coordinate3<T>
+where
+    T: CheckedSub,
+{
+    fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        let x = self.x.checked_sub(rhs.x)?;
+        let y = self.y.checked_sub(rhs.y)?;
+        let z = self.z.checked_sub(rhs.z)?;
+        Some(Coordinate3::new(x, y, z))
+    }
+}
+
+impl<T> Coordinate3<T>
+where
+    T: Integer,
+{
+    pub fn traverse(&self, direction: Direction3) -> Option<Coordinate3<T>> {
+        let Coordinate3 { x, y, z } = *self;
+        match direction {
+            Direction3::North => Some(Coordinate3::new(x, y.checked_decrement()?, z)),
+            Direction3::East => Some(Coordinate3::new(x.checked_increment()?, y, z)),
+            Direction3::South => Some(Coordinate3::new(x, y.checked_increment()?, z)),
+            Direction3::West => Some(Coordinate3::new(x.checked_decrement()?, y, z)),
+            Direction3::Up => Some(Coordinate3::new(x, y, z.checked_increment()?)),
+            Direction3::Down => Some(Coordinate3::new(x, y, z.checked_decrement()?)),
+        }
+    }
+
+    /// Face neighbours. Only the coordinates which don't over or underflow are returned
+    pub fn neighbours_6(&self) -> Vec<Coordinate3<T>> {
+        self.neighbours_changing_up_to(1)
+    }
+
+    /// Face and edge neighbours
+    pub fn neighbours_18(&self) -> Vec<Coordinate3<T>> {
+        self.neighbours_changing_up_to(2)
+    }
+
+    /// Face, edge and corner neighbours
+    pub fn neighbours_26(&self) -> Vec<Coordinate3<T>> {
+        self.neighbours_changing_up_to(3)
+    }
+
+    fn neighbours_changing_up_to(&self, axes: usize) -> Vec<Coordinate3<T>> {
+        CoordinateN::from(*self)
+            .neighbours(axes)
+            .into_iter()
+            .map(Coordinate3::from)
+            .collect()
+    }
+}
+
+/// A coordinate with an arbitrary number of axes. Neighbourhoods are described
+/// by how many axes are allowed to move at once.
+#[derive(Debug, Hash, Copy, Clone, PartialEq, Eq)]
+pub struct CoordinateN<T, const N: usize> {
+    pub axes: [T; N],
+}
+
+impl<T, const N: usize> CoordinateN<T, N> {
+    pub fn new(axes: [T; N]) -> Self {
+        Self { axes }
+    }
+}
+
+impl<T, const N: usize> CheckedAdd for CoordinateN<T, N>
+where
+    T: CheckedAdd + Copy,
+{
+    fn checked_add(&self, rhs: Self) -> Option<Self> {
+        let mut axes = self.axes;
+        for (axis, delta) in axes.iter_mut().zip(rhs.axes) {
+            *axis = axis.checked_add(delta)?;
+        }
+        Some(CoordinateN::new(axes))
+    }
+}
+
+impl<T, const N: usize> CheckedSub for CoordinateN<T, N>
+where
+    T: CheckedSub + Copy,
+{
+    fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        let mut axes = self.axes;
+        for (axis, delta) in axes.iter_mut().zip(rhs.axes) {
+            *axis = axis.checked_sub(delta)?;
+        }
+        Some(CoordinateN::new(axes))
+    }
+}
+
+impl<T, const N: usize> CoordinateN<T, N>
+where
+    T: Integer,
+{
+    /// Every coordinate reachable by moving at most `max_axes` axes by one step.
+    /// `max_axes` of 1 is the von Neumann neighbourhood and `N` is the Moore neighbourhood.
+    pub fn neighbours(&self, max_axes: usize) -> Vec<CoordinateN<T, N>> {
+        let mut neighbours = Vec::new();
+
+        // Each offset is a base 3 number where each digit is one of -1, 0 or +1 for an axis
+        let offsets = 3_usize.pow(N as u32);
+        'offsets: for offset in 0..offsets {
+            let mut axes = self.axes;
+            let mut remaining = offset;
+            let mut moved = 0;
+
+            for axis in axes.iter_mut() {
+                let step = remaining % 3;
+                remaining /= 3;
+
+                *axis = match step {
+                    0 => continue,
+                    1 => match axis.checked_increment() {
+                        Some(a) => a,
+                        None => continue 'offsets,
+                    },
+                    _ => match axis.checked_decrement() {
+                        Some(a) => a,
+                        None => continue 'offsets,
+                    },
+                };
+                moved += 1;
+            }
+
+            if moved > 0 && moved <= max_axes {
+                neighbours.push(CoordinateN::new(axes));
+            }
+        }
+
+        neighbours
+    }
+}
+
+impl<T> From<Coordinate3<T>> for CoordinateN<T, 3> {
+    fn from(value: Coordinate3<T>) -> Self {
+        CoordinateN::new([value.x, value.y, value.z])
+    }
+}
+
+impl<T> From<CoordinateN<T, 3>> for Coordinate3<T> {
+    fn from(value: CoordinateN<T, 3>) -> Self {
+        let [x, y, z] = value.axes;
+        Coordinate3::new(x, y, z)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use strum::VariantArray;
+
+    use super::*;
+
+    #[test]
+    fn traverse_and_reverse_returns_home() {
+        let home = Coordinate3::new(1_usize, 1, 1);
+        for direction in Direction3::VARIANTS {
+            let away = home.traverse(*direction).unwrap();
+            assert_ne!(home, away);
+            assert_eq!(Some(home), away.traverse(direction.reverse()));
+        }
+    }
+
+    #[test]
+    fn traverse_underflow() {
+        let origin = Coordinate3::new(0_usize, 0, 0);
+        assert_eq!(None, origin.traverse(Direction3::West));
+        assert_eq!(None, origin.traverse(Direction3::North));
+        assert_eq!(None, origin.traverse(Direction3::Down));
+        assert_eq!(
+            Some(Coordinate3::new(0, 0, 1)),
+            origin.traverse(Direction3::Up)
+        );
+    }
+
+    #[test]
+    fn neighbourhood_sizes() {
+        let center = Coordinate3::new(5_i32, 5, 5);
+        assert_eq!(6, center.neighbours_6().len());
+        assert_eq!(18, center.neighbours_18().len());
+        assert_eq!(26, center.neighbours_26().len());
+
+        let unique: HashSet<Coordinate3<i32>> = center.neighbours_26().into_iter().collect();
+        assert_eq!(26, unique.len());
+        assert!(!unique.contains(&center));
+    }
+
+    #[test]
+    fn neighbourhood_at_origin_skips_underflow() {
+        let origin = Coordinate3::new(0_usize, 0, 0);
+        assert_eq!(3, origin.neighbours_6().len());
+        assert_eq!(6, origin.neighbours_18().len());
+        assert_eq!(7, origin.neighbours_26().len());
+    }
+
+    #[test]
+    fn neighbours_in_four_dimensions() {
+        let center = CoordinateN::new([0_i64; 4]);
+        assert_eq!(8, center.neighbours(1).len());
+        assert_eq!(80, center.neighbours(4).len());
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let a = Coordinate3::new(1_usize, 2, 3);
+        let b = Coordinate3::new(1_usize, 1, 1);
+        assert_eq!(Some(Coordinate3::new(2, 3, 4)), a.checked_add(b));
+        assert_eq!(Some(Coordinate3::new(0, 1, 2)), a.checked_sub(b));
+        assert_eq!(None, b.checked_sub(a));
+    }
+}
@@ -1,10 +1,13 @@
-use std::{collections::HashMap, fmt::Display, hash::Hash};
+use std::{collections::HashMap, hash::Hash};
 
 use thiserror::Error;
 
-use crate::utils::numbers::{CheckedDecrement, CheckedIncrement, LessThanZero};
+use crate::utils::numbers::{CheckedAdd, Integer};
 
-use super::coordinate::{Coordinate, Direction};
+use super::{
+    coordinate::{Coordinate, Direction},
+    rect::Rect,
+};
 
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum Error {
@@ -30,15 +33,7 @@ where
 
 impl<I, T> Grid<I, T>
 where
-    I: Sized
-        + Copy
-        + Hash
-        + Eq
-        + CheckedIncrement
-        + CheckedDecrement
-        + LessThanZero
-        + Ord
-        + Display,
+    I: Integer,
     T: Sized + Copy,
 {
     /// Don't initialize this with zero values. That's gonna break stuff!
@@ -120,8 +115,16 @@ where
         Ok(Some(strip))
     }
 
+    /// The rectangle of valid indexes, from (0, 0) up to (rows, columns)
+    pub fn bounds(&self) -> Rect<I> {
+        Rect::new(
+            Coordinate::new(I::ZERO, I::ZERO),
+            Coordinate::new(self.rows, self.columns),
+        )
+    }
+
     pub fn valid_index(&self, row: I, column: I) -> bool {
-        self.valid_row(row) && self.valid_column(column)
+        self.bounds().contains(&Coordinate::new(row, column))
     }
 
     // Private methods
@@ -136,28 +139,86 @@ where
             )))
         }
     }
+}
 
-    fn valid_row(&self, row: I) -> bool {
-        if row.less_than_zero() {
-            false
-        } else if row >= self.rows {
-            false
-        } else {
-            true
+impl<I, T> Grid<I, T>
+where
+    I: Integer,
+    T: Sized + Copy,
+{
+    /// Walk from `start` adding `step` each time. Every yielded coordinate is within the grid
+    /// and the iterator ends at the first coordinate which leaves it.
+    pub fn ray(&self, start: Coordinate<I>, step: Coordinate<I>) -> Ray<'_, I, T> {
+        Ray {
+            grid: self,
+            next: Some(start),
+            step,
         }
     }
 
-    fn valid_column(&self, column: I) -> bool {
-        if column.less_than_zero() {
-            false
-        } else if column >= self.columns {
-            false
-        } else {
-            true
+    /// Walk away from `start` (not included) in `direction` and return the first coordinate
+    /// holding a value that matches the predicate. None if we walk off the grid first.
+    pub fn first_hit<P>(
+        &self,
+        start: Coordinate<I>,
+        direction: Direction,
+        predicate: P,
+    ) -> Result<Option<Coordinate<I>>, Error>
+    where
+        P: Fn(T) -> bool,
+    {
+        self.assert_valid_index(start.row, start.column)?;
+
+        let mut coordinate = start;
+        loop {
+            coordinate = match coordinate.traverse(direction) {
+                Some(c) if self.valid_index(c.row, c.column) => c,
+                // Left the grid through either edge
+                _ => return Ok(None),
+            };
+
+            if let Some(t) = self.data.get(&coordinate) {
+                if predicate(*t) {
+                    return Ok(Some(coordinate));
+                }
+            }
         }
     }
 }
 
+/// Iterator produced by `Grid::ray`
+pub struct Ray<'a, I, T>
+where
+    I: Sized + Hash + Eq,
+    T: Sized + Copy,
+{
+    grid: &'a Grid<I, T>,
+    next: Option<Coordinate<I>>,
+    step: Coordinate<I>,
+}
+
+impl<I, T> Iterator for Ray<'_, I, T>
+where
+    I: Integer,
+    T: Sized + Copy,
+{
+    type Item = Coordinate<I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next.take()?;
+        if !self.grid.valid_index(current.row, current.column) {
+            return None;
+        }
+
+        // A zero step would never leave the grid so it only yields the start
+        self.next = current
+            .checked_add(self.step)
+            .filter(|next| next != &current);
+
+        Some(current)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -221,4 +282,61 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_ray_stops_at_edge() {
+        let grid: Grid<i64, char> = Grid::new(5, 5);
+        let ray: Vec<Coordinate<i64>> = grid
+            .ray(Coordinate::new(1, 0), Coordinate::new(1, 2))
+            .collect();
+        assert_eq!(
+            vec![
+                Coordinate::new(1, 0),
+                Coordinate::new(2, 2),
+                Coordinate::new(3, 4)
+            ],
+            ray
+        );
+
+        // A zero step only yields the start rather than repeating it forever
+        let unsigned: Grid<usize, char> = Grid::new(5, 5);
+        let ray: Vec<Coordinate<usize>> = unsigned
+            .ray(Coordinate::new(2, 2), Coordinate::new(0, 0))
+            .collect();
+        assert_eq!(vec![Coordinate::new(2, 2)], ray);
+
+        // Overflow ends the ray the same way as leaving the grid
+        let ray: Vec<Coordinate<usize>> = unsigned
+            .ray(Coordinate::new(2, 2), Coordinate::new(usize::MAX, 0))
+            .collect();
+        assert_eq!(vec![Coordinate::new(2, 2)], ray);
+
+        assert_eq!(
+            0,
+            grid.ray(Coordinate::new(-1, 0), Coordinate::new(1, 0))
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_first_hit() -> Result<(), Error> {
+        let mut grid: Grid<usize, char> = Grid::new(5, 5);
+        grid.set(0, 2, '#')?;
+        grid.set(4, 2, '.')?;
+
+        let start = Coordinate::new(3, 2);
+        assert_eq!(
+            Some(Coordinate::new(0, 2)),
+            grid.first_hit(start, Direction::North, |c| c == '#')?
+        );
+        assert_eq!(None, grid.first_hit(start, Direction::South, |c| c == '#')?);
+        assert_eq!(
+            Some(Coordinate::new(4, 2)),
+            grid.first_hit(start, Direction::South, |_| true)?
+        );
+        assert!(grid
+            .first_hit(Coordinate::new(5, 0), Direction::North, |_| true)
+            .is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,211 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::utils::numbers::Integer;
+
+use super::{
+    coordinate3::{Coordinate3, Direction3},
+    grid::Error,
+};
+
+/// Sparse 3D counterpart to `Grid`. Indexes run from zero up to (not including)
+/// the size of each axis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid3<I, T>
+where
+    I: Sized + Hash + Eq,
+    T: Sized + Copy,
+{
+    x_len: I,
+    y_len: I,
+    z_len: I,
+    data: HashMap<Coordinate3<I>, T>,
+}
+
+impl<I, T> Grid3<I, T>
+where
+    I: Integer,
+    T: Sized + Copy,
+{
+    pub fn new(x_len: I, y_len: I, z_len: I) -> Self {
+        Grid3 {
+            x_len,
+            y_len,
+            z_len,
+            data: HashMap::new(),
+        }
+    }
+
+    /// Create a new grid of the same size as this grid
+    pub fn clone_to_empty(&self) -> Self {
+        Grid3::new(self.x_len, self.y_len, self.z_len)
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn x_len(&self) -> I {
+        self.x_len
+    }
+
+    pub fn y_len(&self) -> I {
+        self.y_len
+    }
+
+    pub fn z_len(&self) -> I {
+        self.z_len
+    }
+
+    /// Read out the value at the specified index. Returned value is copied if present
+    pub fn get(&self, x: I, y: I, z: I) -> Result<Option<T>, Error> {
+        self.assert_valid_index(x, y, z)?;
+        Ok(self.data.get(&Coordinate3::new(x, y, z)).copied())
+    }
+
+    pub fn set(&mut self, x: I, y: I, z: I, val: T) -> Result<(), Error> {
+        self.assert_valid_index(x, y, z)?;
+        self.data.insert(Coordinate3::new(x, y, z), val);
+        Ok(())
+    }
+
+    /// Remove the value at the specified index, returning it if one was present
+    pub fn remove(&mut self, x: I, y: I, z: I) -> Result<Option<T>, Error> {
+        self.assert_valid_index(x, y, z)?;
+        Ok(self.data.remove(&Coordinate3::new(x, y, z)))
+    }
+
+    /// Read values starting at the given index and stepping in `direction`.
+    /// Mirrors `Grid::get_strip`: None is returned if any traversed index is empty
+    pub fn get_strip(
+        &self,
+        x: I,
+        y: I,
+        z: I,
+        len: usize,
+        direction: Direction3,
+    ) -> Result<Option<Vec<T>>, Error> {
+        let mut strip = Vec::with_capacity(len);
+
+        let mut coordinate = Coordinate3::new(x, y, z);
+        for step in 0..len {
+            if step > 0 {
+                coordinate = match coordinate.traverse(direction) {
+                    Some(c) => c,
+                    None => return Err(Error::TraversalError),
+                };
+            }
+            match self.get(coordinate.x, coordinate.y, coordinate.z)? {
+                Some(t) => strip.push(t),
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(strip))
+    }
+
+    /// The face neighbours of an index which lie within the grid
+    pub fn neighbours_6(&self, x: I, y: I, z: I) -> Vec<Coordinate3<I>> {
+        self.in_bounds(Coordinate3::new(x, y, z).neighbours_6())
+    }
+
+    /// The face and edge neighbours of an index which lie within the grid
+    pub fn neighbours_18(&self, x: I, y: I, z: I) -> Vec<Coordinate3<I>> {
+        self.in_bounds(Coordinate3::new(x, y, z).neighbours_18())
+    }
+
+    /// The face, edge and corner neighbours of an index which lie within the grid
+    pub fn neighbours_26(&self, x: I, y: I, z: I) -> Vec<Coordinate3<I>> {
+        self.in_bounds(Coordinate3::new(x, y, z).neighbours_26())
+    }
+
+    pub fn valid_index(&self, x: I, y: I, z: I) -> bool {
+        Self::valid_axis(x, self.x_len)
+            && Self::valid_axis(y, self.y_len)
+            && Self::valid_axis(z, self.z_len)
+    }
+
+    // Private methods
+
+    fn in_bounds(&self, coordinates: Vec<Coordinate3<I>>) -> Vec<Coordinate3<I>> {
+        coordinates
+            .into_iter()
+            .filter(|c| self.valid_index(c.x, c.y, c.z))
+            .collect()
+    }
+
+    fn assert_valid_index(&self, x: I, y: I, z: I) -> Result<(), Error> {
+        if self.valid_index(x, y, z) {
+            Ok(())
+        } else {
+            Err(Error::IndexOutOfBounds(format!(
+                "X: {}, Y: {}, Z: {} is invalid",
+                x, y, z
+            )))
+        }
+    }
+
+    fn valid_axis(value: I, len: I) -> bool {
+        I::ZERO <= value && value < len
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_set_and_get_from_grid3() -> Result<(), Error> {
+        let mut grid: Grid3<usize, u8> = Grid3::new(3, 4, 5);
+        assert_eq!(None, grid.get(2, 3, 4)?);
+
+        grid.set(2, 3, 4, 7)?;
+        assert_eq!(Some(7), grid.get(2, 3, 4)?);
+        assert_eq!(1, grid.len());
+
+        assert_eq!(Some(7), grid.remove(2, 3, 4)?);
+        assert_eq!(0, grid.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_index() {
+        let grid: Grid3<i32, u8> = Grid3::new(2, 2, 2);
+        assert_eq!(
+            Err(Error::IndexOutOfBounds(
+                "X: 0, Y: 2, Z: 0 is invalid".to_string()
+            )),
+            grid.get(0, 2, 0)
+        );
+        assert!(!grid.valid_index(-1, 0, 0));
+    }
+
+    #[test]
+    fn test_get_strip() -> Result<(), Error> {
+        let mut grid: Grid3<usize, char> = Grid3::new(1, 1, 3);
+        grid.set(0, 0, 0, 'a')?;
+        grid.set(0, 0, 1, 'b')?;
+        grid.set(0, 0, 2, 'c')?;
+
+        assert_eq!(
+            Some(vec!['a', 'b', 'c']),
+            grid.get_strip(0, 0, 0, 3, Direction3::Up)?
+        );
+        assert_eq!(
+            Some(vec!['c', 'b']),
+            grid.get_strip(0, 0, 2, 2, Direction3::Down)?
+        );
+        assert!(grid.get_strip(0, 0, 0, 2, Direction3::Down).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbours_clipped_to_grid() {
+        let grid: Grid3<usize, u8> = Grid3::new(3, 3, 3);
+        assert_eq!(3, grid.neighbours_6(0, 0, 0).len());
+        assert_eq!(6, grid.neighbours_6(1, 1, 1).len());
+        assert_eq!(6, grid.neighbours_18(0, 0, 0).len());
+        assert_eq!(18, grid.neighbours_18(1, 1, 1).len());
+        assert_eq!(7, grid.neighbours_26(2, 2, 2).len());
+        assert_eq!(26, grid.neighbours_26(1, 1, 1).len());
+    }
+}
@@ -0,0 +1,328 @@
+use std::{collections::HashMap, hash::Hash};
+
+use strum_macros::{Display, EnumString, VariantArray};
+
+use crate::utils::numbers::{CheckedAdd, CheckedSub, Integer};
+
+use super::grid::Error;
+
+/// Neighbours of a pointy-topped hexagon. Listed clockwise starting from East.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, VariantArray)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDirection {
+    pub fn rotate_clockwise(&self) -> HexDirection {
+        match self {
+            HexDirection::East => HexDirection::SouthEast,
+            HexDirection::SouthEast => HexDirection::SouthWest,
+            HexDirection::SouthWest => HexDirection::West,
+            HexDirection::West => HexDirection::NorthWest,
+            HexDirection::NorthWest => HexDirection::NorthEast,
+            HexDirection::NorthEast => HexDirection::East,
+        }
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> HexDirection {
+        match self {
+            HexDirection::East => HexDirection::NorthEast,
+            HexDirection::SouthEast => HexDirection::East,
+            HexDirection::SouthWest => HexDirection::SouthEast,
+            HexDirection::West => HexDirection::SouthWest,
+            HexDirection::NorthWest => HexDirection::West,
+            HexDirection::NorthEast => HexDirection::NorthWest,
+        }
+    }
+
+    pub fn reverse(&self) -> HexDirection {
+        self.rotate_clockwise()
+            .rotate_clockwise()
+            .rotate_clockwise()
+    }
+}
+
+/// Axial hex coordinate. The implied third cube axis is `s = -q - r`.
+/// `q` grows to the East and `r` grows to the South East.
+#[derive(Debug, Hash, Copy, Clone, PartialEq, Eq)]
+pub struct HexCoordinate<T> {
+    pub q: T,
+    pub r: T,
+}
+
+impl<T> HexCoordinate<T> {
+    pub fn new(q: T, r: T) -> Self {
+        Self { q, r }
+    }
+}
+
+impl<T> CheckedAdd for HexCoordinate<T>
+where
+    T: CheckedAdd,
+{
+    fn checked_add(&self, rhs: Self) -> Option<Self> {
+        let q = self.q.checked_add(rhs.q)?;
+        let r = self.r.checked_add(rhs.r)?;
+        Some(HexCoordinate::new(q, r))
+    }
+}
+
+impl<T> CheckedSub for HexCoordinate<T>
+where
+    T: CheckedSub,
+{
+    fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        let q = self.q.checked_sub(rhs.q)?;
+        let r = self.r.checked_sub(rhs.r)?;
+        Some(HexCoordinate::new(q, r))
+    }
+}
+
+impl<T> HexCoordinate<T>
+where
+    T: Integer,
+{
+    pub fn traverse(&self, direction: HexDirection) -> Option<HexCoordinate<T>> {
+        let HexCoordinate { q, r } = *self;
+        match direction {
+            HexDirection::East => Some(HexCoordinate::new(q.checked_increment()?, r)),
+            HexDirection::SouthEast => Some(HexCoordinate::new(q, r.checked_increment()?)),
+            HexDirection::SouthWest => Some(HexCoordinate::new(
+                q.checked_decrement()?,
+                r.checked_increment()?,
+            )),
+            HexDirection::West => Some(HexCoordinate::new(q.checked_decrement()?, r)),
+            HexDirection::NorthWest => Some(HexCoordinate::new(q, r.checked_decrement()?)),
+            HexDirection::NorthEast => Some(HexCoordinate::new(
+                q.checked_increment()?,
+                r.checked_decrement()?,
+            )),
+        }
+    }
+
+    /// All neighbours which can be represented, in clockwise order from East
+    pub fn neighbours(&self) -> Vec<HexCoordinate<T>> {
+        [
+            HexDirection::East,
+            HexDirection::SouthEast,
+            HexDirection::SouthWest,
+            HexDirection::West,
+            HexDirection::NorthWest,
+            HexDirection::NorthEast,
+        ]
+        .iter()
+        .filter_map(|direction| self.traverse(*direction))
+        .collect()
+    }
+}
+
+impl<T> HexCoordinate<T>
+where
+    T: Integer,
+{
+    /// Number of steps between two hexes. None if the math overflows
+    pub fn distance(&self, other: &HexCoordinate<T>) -> Option<T> {
+        let dq = Self::abs_diff(self.q, other.q)?;
+        let dr = Self::abs_diff(self.r, other.r)?;
+        // The difference along the implied s axis is the difference of q + r
+        let ds = Self::abs_diff(self.q.checked_add(self.r)?, other.q.checked_add(other.r)?)?;
+
+        Some(dq.max(dr).max(ds))
+    }
+
+    fn abs_diff(a: T, b: T) -> Option<T> {
+        a.max(b).checked_sub(a.min(b))
+    }
+}
+
+impl<T> HexCoordinate<T>
+where
+    T: Integer,
+{
+    /// Rotate 60 degrees clockwise around `center`
+    pub fn rotate_clockwise(&self, center: &HexCoordinate<T>) -> Option<HexCoordinate<T>> {
+        let offset = self.checked_sub(*center)?;
+        // (q, r, s) -> (-r, -s, -q)
+        let q = T::ZERO.checked_sub(offset.r)?;
+        let r = offset.q.checked_add(offset.r)?;
+        HexCoordinate::new(q, r).checked_add(*center)
+    }
+
+    /// Rotate 60 degrees counter clockwise around `center`
+    pub fn rotate_counter_clockwise(&self, center: &HexCoordinate<T>) -> Option<HexCoordinate<T>> {
+        let offset = self.checked_sub(*center)?;
+        // (q, r, s) -> (-s, -q, -r)
+        let q = offset.q.checked_add(offset.r)?;
+        let r = T::ZERO.checked_sub(offset.q)?;
+        HexCoordinate::new(q, r).checked_add(*center)
+    }
+}
+
+/// Sparse hexagon shaped grid centered on the origin. Any coordinate within
+/// `radius` steps of the origin is a valid index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexGrid<I, T>
+where
+    I: Sized + Hash + Eq,
+    T: Sized + Copy,
+{
+    radius: I,
+    data: HashMap<HexCoordinate<I>, T>,
+}
+
+impl<I, T> HexGrid<I, T>
+where
+    I: Integer,
+    T: Sized + Copy,
+{
+    pub fn new(radius: I) -> Self {
+        HexGrid {
+            radius,
+            data: HashMap::new(),
+        }
+    }
+
+    /// Create a new grid of the same size as this grid
+    pub fn clone_to_empty(&self) -> Self {
+        HexGrid::new(self.radius)
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn radius(&self) -> I {
+        self.radius
+    }
+
+    /// Read out the value at the specified index. Returned value is copied if present
+    pub fn get(&self, q: I, r: I) -> Result<Option<T>, Error> {
+        self.assert_valid_index(q, r)?;
+        Ok(self.data.get(&HexCoordinate::new(q, r)).copied())
+    }
+
+    pub fn set(&mut self, q: I, r: I, val: T) -> Result<(), Error> {
+        self.assert_valid_index(q, r)?;
+        self.data.insert(HexCoordinate::new(q, r), val);
+        Ok(())
+    }
+
+    pub fn valid_index(&self, q: I, r: I) -> bool {
+        let origin = HexCoordinate::new(I::ZERO, I::ZERO);
+        match HexCoordinate::new(q, r).distance(&origin) {
+            Some(distance) => distance <= self.radius,
+            None => false,
+        }
+    }
+
+    // Private methods
+
+    fn assert_valid_index(&self, q: I, r: I) -> Result<(), Error> {
+        if self.valid_index(q, r) {
+            Ok(())
+        } else {
+            Err(Error::IndexOutOfBounds(format!(
+                "Q: {}, R: {} is invalid",
+                q, r
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use strum::VariantArray;
+
+    use super::*;
+
+    #[test]
+    fn direction_rotation_cycles() {
+        for direction in HexDirection::VARIANTS {
+            let mut rotated = *direction;
+            for _ in 0..6 {
+                rotated = rotated.rotate_clockwise();
+            }
+            assert_eq!(*direction, rotated);
+            assert_eq!(
+                *direction,
+                direction.rotate_clockwise().rotate_counter_clockwise()
+            );
+        }
+    }
+
+    #[test]
+    fn traverse_and_reverse_returns_home() {
+        let home = HexCoordinate::new(0_i32, 0);
+        for direction in HexDirection::VARIANTS {
+            let away = home.traverse(*direction).unwrap();
+            assert_eq!(Some(1), home.distance(&away));
+            assert_eq!(Some(home), away.traverse(direction.reverse()));
+        }
+    }
+
+    #[test]
+    fn neighbours_are_all_one_step_away() {
+        let center = HexCoordinate::new(3_i64, -2);
+        let neighbours = center.neighbours();
+        assert_eq!(6, neighbours.len());
+        for neighbour in neighbours {
+            assert_eq!(Some(1), center.distance(&neighbour));
+        }
+    }
+
+    #[test]
+    fn distance() {
+        let a = HexCoordinate::new(0_i32, 0);
+        assert_eq!(Some(0), a.distance(&a));
+        assert_eq!(Some(3), a.distance(&HexCoordinate::new(3, -3)));
+        assert_eq!(Some(3), a.distance(&HexCoordinate::new(-1, -2)));
+        assert_eq!(Some(5), a.distance(&HexCoordinate::new(2, 3)));
+    }
+
+    #[test]
+    fn rotation_follows_directions() {
+        let center = HexCoordinate::new(1_i32, 1);
+        for direction in HexDirection::VARIANTS {
+            let neighbour = center.traverse(*direction).unwrap();
+            assert_eq!(
+                center.traverse(direction.rotate_clockwise()),
+                neighbour.rotate_clockwise(&center)
+            );
+            assert_eq!(
+                center.traverse(direction.rotate_counter_clockwise()),
+                neighbour.rotate_counter_clockwise(&center)
+            );
+        }
+    }
+
+    #[test]
+    fn hex_grid_bounds() -> Result<(), Error> {
+        let mut grid: HexGrid<i32, char> = HexGrid::new(1);
+        assert_eq!(None, grid.get(0, 0)?);
+        grid.set(1, -1, 'a')?;
+        assert_eq!(Some('a'), grid.get(1, -1)?);
+        assert_eq!(1, grid.len());
+
+        // Only 7 hexes fit within a radius of 1
+        let mut valid = 0;
+        for q in -2..=2 {
+            for r in -2..=2 {
+                if grid.valid_index(q, r) {
+                    valid += 1;
+                }
+            }
+        }
+        assert_eq!(7, valid);
+
+        assert_eq!(
+            Err(Error::IndexOutOfBounds("Q: 1, R: 1 is invalid".to_string())),
+            grid.get(1, 1)
+        );
+        Ok(())
+    }
+}
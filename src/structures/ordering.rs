@@ -0,0 +1,258 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    /// The rules contradict each other so no order exists. Holds the cycle formatted as
+    /// `a -> b -> a`
+    #[error("Ordering rules form a cycle: {0}")]
+    Cycle(String),
+}
+
+/// `before` has to appear somewhere ahead of `after`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule<T> {
+    pub before: T,
+    pub after: T,
+}
+
+impl<T> Rule<T> {
+    pub fn new(before: T, after: T) -> Self {
+        Rule { before, after }
+    }
+}
+
+/// A set of "X comes before Y" rules stored as a directed graph. Rules aren't assumed
+/// to be transitive, only the rules added directly are checked.
+#[derive(Debug, Clone, Default)]
+pub struct OrderingRules<T>
+where
+    T: Copy + Eq + Hash,
+{
+    successors: HashMap<T, HashSet<T>>,
+}
+
+impl<T> OrderingRules<T>
+where
+    T: Copy + Eq + Hash + Debug,
+{
+    pub fn new() -> Self {
+        OrderingRules {
+            successors: HashMap::new(),
+        }
+    }
+
+    pub fn add_rule(&mut self, rule: Rule<T>) {
+        self.successors
+            .entry(rule.before)
+            .or_default()
+            .insert(rule.after);
+    }
+
+    pub fn contains(&self, rule: Rule<T>) -> bool {
+        self.successors
+            .get(&rule.before)
+            .is_some_and(|after| after.contains(&rule.after))
+    }
+
+    /// The first rule broken while reading the sequence front to back, i.e. the earliest
+    /// element which should have come after something later in the sequence
+    pub fn first_violation(&self, sequence: &[T]) -> Option<Rule<T>> {
+        for (i, earlier) in sequence.iter().enumerate() {
+            for later in &sequence[i + 1..] {
+                let rule = Rule::new(*later, *earlier);
+                if self.contains(rule) {
+                    return Some(rule);
+                }
+            }
+        }
+        None
+    }
+
+    pub fn is_ordered(&self, sequence: &[T]) -> bool {
+        self.first_violation(sequence).is_none()
+    }
+
+    /// Order the given nodes using only the rules between them (Kahn's algorithm).
+    /// Nodes which aren't constrained relative to each other keep their input order.
+    /// Nodes are expected to be unique
+    pub fn sort_subset(&self, nodes: &[T]) -> Result<Vec<T>, Error> {
+        let index: HashMap<T, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+
+        let mut incoming = vec![0_usize; nodes.len()];
+        for node in nodes {
+            for after in self.successors_within(*node, &index) {
+                incoming[after] += 1;
+            }
+        }
+
+        // Always take the earliest ready node in the input to keep the result stable
+        let mut ready: BTreeSet<usize> = (0..nodes.len()).filter(|i| incoming[*i] == 0).collect();
+        let mut sorted = Vec::with_capacity(nodes.len());
+        while let Some(current) = ready.pop_first() {
+            sorted.push(nodes[current]);
+            for after in self.successors_within(nodes[current], &index) {
+                incoming[after] -= 1;
+                if incoming[after] == 0 {
+                    ready.insert(after);
+                }
+            }
+        }
+
+        if sorted.len() < nodes.len() {
+            // Whatever is left over sits on or behind a cycle
+            let remaining: Vec<T> = (0..nodes.len())
+                .filter(|i| incoming[*i] > 0)
+                .map(|i| nodes[i])
+                .collect();
+            let cycle = self
+                .cycle_within(&remaining)
+                .expect("Kahn's algorithm only stalls on a cycle");
+            return Err(Error::Cycle(format_cycle(&cycle)));
+        }
+
+        Ok(sorted)
+    }
+
+    // Private methods
+
+    /// Input positions of the nodes which have to come after `node`
+    fn successors_within<'a>(
+        &'a self,
+        node: T,
+        index: &'a HashMap<T, usize>,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.successors
+            .get(&node)
+            .into_iter()
+            .flatten()
+            .filter_map(|after| index.get(after).copied())
+    }
+
+    /// Depth first search over the rules between `nodes`, returning the first cycle found
+    fn cycle_within(&self, nodes: &[T]) -> Option<Vec<T>> {
+        let index: HashMap<T, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let mut finished = vec![false; nodes.len()];
+        let mut on_path = vec![false; nodes.len()];
+
+        for start in 0..nodes.len() {
+            if finished[start] {
+                continue;
+            }
+
+            // Explicit stack of (node, its unexplored successors) so long chains can't
+            // overflow the call stack
+            let mut path = vec![start];
+            let mut pending = vec![self
+                .successors_within(nodes[start], &index)
+                .collect::<Vec<_>>()];
+            on_path[start] = true;
+
+            while let Some(successors) = pending.last_mut() {
+                match successors.pop() {
+                    Some(next) if on_path[next] => {
+                        let from = path.iter().position(|n| *n == next).expect("On path");
+                        let mut cycle: Vec<T> = path[from..].iter().map(|i| nodes[*i]).collect();
+                        cycle.push(nodes[next]);
+                        return Some(cycle);
+                    }
+                    Some(next) if !finished[next] => {
+                        on_path[next] = true;
+                        path.push(next);
+                        pending.push(self.successors_within(nodes[next], &index).collect());
+                    }
+                    Some(_) => (),
+                    None => {
+                        let done = path.pop().expect("Path and pending are the same length");
+                        on_path[done] = false;
+                        finished[done] = true;
+                        pending.pop();
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<T> FromIterator<Rule<T>> for OrderingRules<T>
+where
+    T: Copy + Eq + Hash + Debug,
+{
+    fn from_iter<I: IntoIterator<Item = Rule<T>>>(iter: I) -> Self {
+        let mut rules = OrderingRules::new();
+        for rule in iter {
+            rules.add_rule(rule);
+        }
+        rules
+    }
+}
+
+fn format_cycle<T: Debug>(cycle: &[T]) -> String {
+    cycle
+        .iter()
+        .map(|node| format!("{:?}", node))
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rules(pairs: &[(i32, i32)]) -> OrderingRules<i32> {
+        pairs.iter().map(|(b, a)| Rule::new(*b, *a)).collect()
+    }
+
+    #[test]
+    fn check_sequences() {
+        let rules = rules(&[(47, 53), (97, 13), (97, 61), (75, 47), (61, 13)]);
+        assert!(rules.is_ordered(&[75, 47, 61, 53, 29]));
+        assert!(rules.is_ordered(&[]));
+        assert_eq!(
+            Some(Rule::new(61, 13)),
+            rules.first_violation(&[13, 61, 97])
+        );
+        assert_eq!(
+            Some(Rule::new(47, 53)),
+            rules.first_violation(&[75, 53, 47])
+        );
+    }
+
+    #[test]
+    fn sort_only_uses_rules_within_the_subset() -> Result<(), Error> {
+        let rules = rules(&[(97, 75), (75, 47), (47, 61), (61, 53), (29, 13), (53, 29)]);
+        assert_eq!(
+            vec![97, 75, 47, 61, 53],
+            rules.sort_subset(&[75, 97, 47, 61, 53])?
+        );
+        assert_eq!(vec![61, 29, 13], rules.sort_subset(&[61, 13, 29])?);
+
+        // No rules between 97 and 13 so they stay in input order
+        assert_eq!(vec![13, 97], rules.sort_subset(&[13, 97])?);
+        Ok(())
+    }
+
+    #[test]
+    fn cycles_are_reported() {
+        // A full cycle only matters if the subset contains every node on it
+        let rules = rules(&[(1, 2), (2, 3), (3, 1), (3, 4)]);
+        assert_eq!(Ok(vec![4, 1, 2]), rules.sort_subset(&[2, 4, 1]));
+        assert_eq!(
+            Err(Error::Cycle("1 -> 2 -> 3 -> 1".to_string())),
+            rules.sort_subset(&[4, 1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn acyclic_rules_sort_every_node() {
+        let rules = rules(&[(1, 2), (1, 3), (2, 4), (3, 4)]);
+        assert_eq!(Ok(vec![1, 3, 2, 4]), rules.sort_subset(&[4, 3, 2, 1]));
+    }
+}
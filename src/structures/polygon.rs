@@ -0,0 +1,107 @@
+use crate::utils::numbers::{gcd, Integer};
+
+use super::coordinate::Coordinate;
+
+/// Twice the area enclosed by a simple polygon given as its vertices in order, using the
+/// shoelace formula. Doubled so the result stays an exact integer. The polygon is closed
+/// automatically so the first vertex shouldn't be repeated at the end.
+pub fn doubled_area<T>(vertices: &[Coordinate<T>]) -> T
+where
+    T: Integer,
+{
+    let mut positive = T::ZERO;
+    let mut negative = T::ZERO;
+
+    for (i, current) in vertices.iter().enumerate() {
+        let next = &vertices[(i + 1) % vertices.len()];
+        // Split the cross products by sign so unsigned types never go below zero
+        positive = positive + current.row * next.column;
+        negative = negative + current.column * next.row;
+    }
+
+    positive.max(negative) - positive.min(negative)
+}
+
+/// Number of lattice points on the edges of the polygon, including the vertices
+pub fn boundary_points<T>(vertices: &[Coordinate<T>]) -> T
+where
+    T: Integer,
+{
+    let mut count = T::ZERO;
+
+    for (i, current) in vertices.iter().enumerate() {
+        let next = &vertices[(i + 1) % vertices.len()];
+        let row_distance = current.row.max(next.row) - current.row.min(next.row);
+        let column_distance = current.column.max(next.column) - current.column.min(next.column);
+        // Each edge holds gcd lattice steps, counting one end of the edge
+        count = count + gcd(row_distance, column_distance);
+    }
+
+    count
+}
+
+/// Number of lattice points strictly inside the polygon, using Pick's theorem:
+/// `area = interior + boundary / 2 - 1`
+pub fn interior_points<T>(vertices: &[Coordinate<T>]) -> T
+where
+    T: Integer,
+{
+    let two = T::ONE + T::ONE;
+    // Rearranged to keep every intermediate value non-negative
+    (doubled_area(vertices) + two - boundary_points(vertices)) / two
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn square(size: i64) -> Vec<Coordinate<i64>> {
+        vec![
+            Coordinate::new(0, 0),
+            Coordinate::new(0, size),
+            Coordinate::new(size, size),
+            Coordinate::new(size, 0),
+        ]
+    }
+
+    #[test]
+    fn area_of_square() {
+        assert_eq!(32, doubled_area(&square(4)));
+
+        // Winding order doesn't matter
+        let mut reversed = square(4);
+        reversed.reverse();
+        assert_eq!(32, doubled_area(&reversed));
+    }
+
+    #[test]
+    fn area_of_triangle() {
+        let triangle = vec![
+            Coordinate::new(0_usize, 0),
+            Coordinate::new(0, 3),
+            Coordinate::new(1, 0),
+        ];
+        assert_eq!(3, doubled_area(&triangle));
+        assert_eq!(5, boundary_points(&triangle));
+        assert_eq!(0, interior_points(&triangle));
+    }
+
+    #[test]
+    fn picks_theorem() {
+        assert_eq!(16, boundary_points(&square(4)));
+        assert_eq!(9, interior_points(&square(4)));
+
+        // An L shape: 3x3 square with the bottom right 2x2 removed
+        let l_shape = vec![
+            Coordinate::new(0_i32, 0),
+            Coordinate::new(0, 3),
+            Coordinate::new(1, 3),
+            Coordinate::new(1, 1),
+            Coordinate::new(3, 1),
+            Coordinate::new(3, 0),
+        ];
+        assert_eq!(10, doubled_area(&l_shape));
+        assert_eq!(12, boundary_points(&l_shape));
+        assert_eq!(0, interior_points(&l_shape));
+    }
+}
@@ -0,0 +1,254 @@
+use crate::utils::numbers::Integer;
+
+use super::coordinate::Coordinate;
+
+/// Axis aligned rectangle of coordinates. The top left corner is included and the
+/// bottom right corner is not, the same way `Grid` treats `rows` and `columns`.
+#[derive(Debug, Hash, Copy, Clone, PartialEq, Eq)]
+pub struct Rect<I> {
+    pub top_left: Coordinate<I>,
+    pub bottom_right: Coordinate<I>,
+}
+
+impl<I> Rect<I>
+where
+    I: Integer,
+{
+    pub fn new(top_left: Coordinate<I>, bottom_right: Coordinate<I>) -> Self {
+        Rect {
+            top_left,
+            bottom_right,
+        }
+    }
+
+    /// True when the rectangle doesn't hold any coordinates
+    pub fn is_empty(&self) -> bool {
+        self.top_left.row >= self.bottom_right.row
+            || self.top_left.column >= self.bottom_right.column
+    }
+
+    pub fn contains(&self, coordinate: &Coordinate<I>) -> bool {
+        self.top_left.row <= coordinate.row
+            && coordinate.row < self.bottom_right.row
+            && self.top_left.column <= coordinate.column
+            && coordinate.column < self.bottom_right.column
+    }
+
+    /// True when every coordinate of `other` is also in this rectangle
+    pub fn contains_rect(&self, other: &Rect<I>) -> bool {
+        other.is_empty()
+            || (self.top_left.row <= other.top_left.row
+                && self.top_left.column <= other.top_left.column
+                && other.bottom_right.row <= self.bottom_right.row
+                && other.bottom_right.column <= self.bottom_right.column)
+    }
+
+    /// The coordinates held by both rectangles. None if they don't overlap
+    pub fn intersection(&self, other: &Rect<I>) -> Option<Rect<I>> {
+        let overlap = Rect::new(
+            Coordinate::new(
+                self.top_left.row.max(other.top_left.row),
+                self.top_left.column.max(other.top_left.column),
+            ),
+            Coordinate::new(
+                self.bottom_right.row.min(other.bottom_right.row),
+                self.bottom_right.column.min(other.bottom_right.column),
+            ),
+        );
+
+        match overlap.is_empty() {
+            true => None,
+            false => Some(overlap),
+        }
+    }
+
+    /// The smallest rectangle holding both rectangles
+    pub fn union(&self, other: &Rect<I>) -> Rect<I> {
+        if self.is_empty() {
+            return *other;
+        } else if other.is_empty() {
+            return *self;
+        }
+
+        Rect::new(
+            Coordinate::new(
+                self.top_left.row.min(other.top_left.row),
+                self.top_left.column.min(other.top_left.column),
+            ),
+            Coordinate::new(
+                self.bottom_right.row.max(other.bottom_right.row),
+                self.bottom_right.column.max(other.bottom_right.column),
+            ),
+        )
+    }
+}
+
+impl<I> Rect<I>
+where
+    I: Integer,
+{
+    /// The smallest rectangle holding every coordinate. None when there are no
+    /// coordinates or the bottom right corner can't be represented.
+    pub fn bounding_box<C>(coordinates: C) -> Option<Rect<I>>
+    where
+        C: IntoIterator<Item = Coordinate<I>>,
+    {
+        let mut coordinates = coordinates.into_iter();
+        let first = coordinates.next()?;
+
+        let mut top_left = first;
+        let mut bottom_right = first;
+        for coordinate in coordinates {
+            top_left.row = top_left.row.min(coordinate.row);
+            top_left.column = top_left.column.min(coordinate.column);
+            bottom_right.row = bottom_right.row.max(coordinate.row);
+            bottom_right.column = bottom_right.column.max(coordinate.column);
+        }
+
+        // The bottom right corner sits one past the last coordinate
+        let bottom_right = Coordinate::new(
+            bottom_right.row.checked_increment()?,
+            bottom_right.column.checked_increment()?,
+        );
+
+        Some(Rect::new(top_left, bottom_right))
+    }
+
+    /// Walk every coordinate in the rectangle row by row
+    pub fn iter(&self) -> RectIter<I> {
+        RectIter {
+            rect: *self,
+            next: match self.is_empty() {
+                true => None,
+                false => Some(self.top_left),
+            },
+        }
+    }
+}
+
+impl<I> Rect<I>
+where
+    I: Integer,
+{
+    pub fn rows(&self) -> I {
+        self.bottom_right.row - self.top_left.row
+    }
+
+    pub fn columns(&self) -> I {
+        self.bottom_right.column - self.top_left.column
+    }
+
+    /// Number of coordinates in the rectangle. Empty rectangles must not be inverted
+    pub fn area(&self) -> I {
+        self.rows() * self.columns()
+    }
+}
+
+/// Iterator produced by `Rect::iter`
+pub struct RectIter<I> {
+    rect: Rect<I>,
+    next: Option<Coordinate<I>>,
+}
+
+impl<I> Iterator for RectIter<I>
+where
+    I: Integer,
+{
+    type Item = Coordinate<I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next.take()?;
+
+        // Move along the row, wrapping onto the next row at the right hand edge
+        let column = current.column.checked_increment();
+        self.next = match column {
+            Some(column) if column < self.rect.bottom_right.column => {
+                Some(Coordinate::new(current.row, column))
+            }
+            _ => current
+                .row
+                .checked_increment()
+                .filter(|row| row < &self.rect.bottom_right.row)
+                .map(|row| Coordinate::new(row, self.rect.top_left.column)),
+        };
+
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rect(top: i32, left: i32, bottom: i32, right: i32) -> Rect<i32> {
+        Rect::new(Coordinate::new(top, left), Coordinate::new(bottom, right))
+    }
+
+    #[test]
+    fn contains() {
+        let r = rect(0, 0, 2, 3);
+        assert!(r.contains(&Coordinate::new(0, 0)));
+        assert!(r.contains(&Coordinate::new(1, 2)));
+        assert!(!r.contains(&Coordinate::new(2, 0)));
+        assert!(!r.contains(&Coordinate::new(0, 3)));
+        assert!(!r.contains(&Coordinate::new(-1, 0)));
+
+        assert!(r.contains_rect(&rect(1, 1, 2, 3)));
+        assert!(!r.contains_rect(&rect(1, 1, 3, 3)));
+        assert!(r.contains_rect(&rect(5, 5, 5, 5)));
+    }
+
+    #[test]
+    fn intersection_and_union() {
+        let a = rect(0, 0, 4, 4);
+        let b = rect(2, 3, 6, 8);
+        assert_eq!(Some(rect(2, 3, 4, 4)), a.intersection(&b));
+        assert_eq!(rect(0, 0, 6, 8), a.union(&b));
+        assert_eq!(None, a.intersection(&rect(4, 0, 6, 4)));
+        assert_eq!(a, a.union(&rect(10, 10, 10, 10)));
+    }
+
+    #[test]
+    fn area() {
+        assert_eq!(12, rect(0, 0, 3, 4).area());
+        assert_eq!(4, rect(-1, -1, 1, 1).area());
+        assert_eq!(0, rect(1, 1, 1, 5).area());
+    }
+
+    #[test]
+    fn bounding_box() {
+        let coordinates = vec![
+            Coordinate::new(3, -2),
+            Coordinate::new(0, 4),
+            Coordinate::new(1, 1),
+        ];
+        let bounds = Rect::bounding_box(coordinates.clone()).unwrap();
+        assert_eq!(rect(0, -2, 4, 5), bounds);
+        for coordinate in coordinates {
+            assert!(bounds.contains(&coordinate));
+        }
+
+        assert_eq!(None, Rect::<i32>::bounding_box(Vec::new()));
+        // The corner past u32::MAX can't be represented
+        assert_eq!(
+            None,
+            Rect::bounding_box(vec![Coordinate::new(0_u32, u32::MAX)])
+        );
+    }
+
+    #[test]
+    fn iterate() {
+        let coordinates: Vec<Coordinate<i32>> = rect(1, 1, 3, 3).iter().collect();
+        assert_eq!(
+            vec![
+                Coordinate::new(1, 1),
+                Coordinate::new(1, 2),
+                Coordinate::new(2, 1),
+                Coordinate::new(2, 2)
+            ],
+            coordinates
+        );
+        assert_eq!(0, rect(0, 0, 0, 3).iter().count());
+        assert_eq!(15, rect(0, 0, 3, 5).iter().count());
+    }
+}
@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+
+use crate::utils::numbers::Integer;
+
+use super::{
+    coordinate::{Coordinate, Direction},
+    grid::{Error, Grid},
+};
+
+/// What a walker does next, decided by its rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Step forward one cell in the current direction
+    Move,
+
+    /// Face a new direction without moving. Counts as a step
+    Turn(Direction),
+
+    /// End the walk where it is
+    Stop,
+}
+
+/// How a walk ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination<I> {
+    /// The walker came back to a position it had already stood on facing the same
+    /// way, so it would repeat forever. `length` counts the steps in one lap
+    Cycle {
+        entry: Coordinate<I>,
+        length: usize,
+        direction: Direction,
+    },
+
+    /// Moved off the grid from `exit`, the last position on it
+    LeftGrid {
+        exit: Coordinate<I>,
+        direction: Direction,
+    },
+
+    /// The rule asked to stop
+    Stopped {
+        position: Coordinate<I>,
+        direction: Direction,
+    },
+}
+
+/// One state of the walk, yielded before the rule acts on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkStep<I> {
+    pub position: Coordinate<I>,
+    pub direction: Direction,
+}
+
+/// Walks a grid one step at a time following a rule. The rule is given the current
+/// position, the direction faced and the value in the cell ahead (None when that cell
+/// is empty or off the grid). Every position is remembered with the step it was first
+/// reached facing each direction, which is how cycles get spotted.
+pub struct Walker<'a, I, T, R>
+where
+    I: Integer,
+    T: Sized + Copy,
+{
+    grid: &'a Grid<I, T>,
+    position: Coordinate<I>,
+    direction: Direction,
+    rule: R,
+    steps: usize,
+    visited: HashMap<Coordinate<I>, HashMap<Direction, usize>>,
+    termination: Option<Termination<I>>,
+}
+
+impl<'a, I, T, R> Walker<'a, I, T, R>
+where
+    I: Integer,
+    T: Sized + Copy,
+    R: FnMut(Coordinate<I>, Direction, Option<T>) -> Action,
+{
+    /// Errors if the start isn't on the grid
+    pub fn new(
+        grid: &'a Grid<I, T>,
+        start: Coordinate<I>,
+        direction: Direction,
+        rule: R,
+    ) -> Result<Self, Error> {
+        // Only for the bounds check
+        grid.get(start.row, start.column)?;
+
+        Ok(Walker {
+            grid,
+            position: start,
+            direction,
+            rule,
+            steps: 0,
+            visited: HashMap::new(),
+            termination: None,
+        })
+    }
+
+    /// Walk until the rule stops, the walker leaves the grid or it starts going round in
+    /// circles
+    pub fn run(&mut self) -> Termination<I> {
+        self.by_ref().for_each(drop);
+        self.termination
+            .expect("The walk only ends once terminated")
+    }
+
+    /// None while the walk is still going
+    pub fn termination(&self) -> Option<Termination<I>> {
+        self.termination
+    }
+
+    /// Number of steps taken so far, moves and turns alike
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Number of distinct positions stood on, whichever way the walker faced
+    pub fn positions_visited(&self) -> usize {
+        self.visited.len()
+    }
+
+    pub fn visited_positions(&self) -> impl Iterator<Item = &Coordinate<I>> {
+        self.visited.keys()
+    }
+
+    pub fn has_visited(&self, position: Coordinate<I>, direction: Direction) -> bool {
+        self.visited
+            .get(&position)
+            .is_some_and(|directions| directions.contains_key(&direction))
+    }
+
+    // Private methods
+
+    /// The cell ahead if it's on the grid
+    fn ahead(&self) -> Option<Coordinate<I>> {
+        self.position
+            .traverse(self.direction)
+            .filter(|next| self.grid.valid_index(next.row, next.column))
+    }
+}
+
+impl<I, T, R> Iterator for Walker<'_, I, T, R>
+where
+    I: Integer,
+    T: Sized + Copy,
+    R: FnMut(Coordinate<I>, Direction, Option<T>) -> Action,
+{
+    type Item = WalkStep<I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.termination.is_some() {
+            return None;
+        }
+
+        let (position, direction) = (self.position, self.direction);
+        let directions = self.visited.entry(position).or_default();
+        if let Some(first_step) = directions.get(&direction) {
+            self.termination = Some(Termination::Cycle {
+                entry: position,
+                length: self.steps - first_step,
+                direction,
+            });
+            return None;
+        }
+        directions.insert(direction, self.steps);
+        self.steps += 1;
+
+        let ahead = self.ahead();
+        let value = ahead.and_then(|next| self.grid.get(next.row, next.column).ok().flatten());
+        match (self.rule)(position, direction, value) {
+            Action::Move => match ahead {
+                Some(next) => self.position = next,
+                None => {
+                    self.termination = Some(Termination::LeftGrid {
+                        exit: position,
+                        direction,
+                    })
+                }
+            },
+            Action::Turn(facing) => self.direction = facing,
+            Action::Stop => {
+                self.termination = Some(Termination::Stopped {
+                    position,
+                    direction,
+                })
+            }
+        }
+
+        Some(WalkStep {
+            position,
+            direction,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Obstacles marked with '#', every other cell '.'
+    fn grid(lines: &[&str]) -> Grid<usize, char> {
+        let mut grid = Grid::new(lines.len(), lines[0].len());
+        for (row, line) in lines.iter().enumerate() {
+            for (column, c) in line.chars().enumerate() {
+                grid.set(row, column, c).unwrap();
+            }
+        }
+        grid
+    }
+
+    fn turn_right_at_walls(
+        _: Coordinate<usize>,
+        direction: Direction,
+        ahead: Option<char>,
+    ) -> Action {
+        match ahead {
+            Some('#') => Action::Turn(direction.turn_right()),
+            _ => Action::Move,
+        }
+    }
+
+    #[test]
+    fn walks_off_the_grid() -> Result<(), Error> {
+        let grid = grid(&["..#", "...", "..."]);
+        let mut walker = Walker::new(
+            &grid,
+            Coordinate::new(2, 2),
+            Direction::North,
+            turn_right_at_walls,
+        )?;
+
+        let steps: Vec<WalkStep<usize>> = walker.by_ref().collect();
+        assert_eq!(3, steps.len());
+        assert_eq!(
+            WalkStep {
+                position: Coordinate::new(1, 2),
+                direction: Direction::East
+            },
+            steps[2]
+        );
+        assert_eq!(
+            Some(Termination::LeftGrid {
+                exit: Coordinate::new(1, 2),
+                direction: Direction::East
+            }),
+            walker.termination()
+        );
+        assert_eq!(2, walker.positions_visited());
+        assert!(walker.has_visited(Coordinate::new(1, 2), Direction::North));
+        Ok(())
+    }
+
+    #[test]
+    fn detects_cycles() -> Result<(), Error> {
+        let grid = grid(&[".#...", "....#", "#....", "...#."]);
+        let mut walker = Walker::new(
+            &grid,
+            Coordinate::new(2, 1),
+            Direction::North,
+            turn_right_at_walls,
+        )?;
+        assert_eq!(
+            Termination::Cycle {
+                entry: Coordinate::new(2, 1),
+                length: 10,
+                direction: Direction::North
+            },
+            walker.run()
+        );
+        assert_eq!(None, walker.next());
+        Ok(())
+    }
+
+    #[test]
+    fn rules_can_stop() -> Result<(), Error> {
+        let grid = grid(&["....", "...."]);
+        // Stops after three cells
+        let mut walker = Walker::new(
+            &grid,
+            Coordinate::new(0, 0),
+            Direction::East,
+            |position: Coordinate<usize>, _, _| match position.column {
+                2 => Action::Stop,
+                _ => Action::Move,
+            },
+        )?;
+        assert_eq!(
+            Termination::Stopped {
+                position: Coordinate::new(0, 2),
+                direction: Direction::East
+            },
+            walker.run()
+        );
+        assert_eq!(3, walker.steps());
+        Ok(())
+    }
+
+    #[test]
+    fn start_must_be_on_the_grid() {
+        let grid = grid(&["."]);
+        assert!(Walker::new(
+            &grid,
+            Coordinate::new(1, 0),
+            Direction::North,
+            turn_right_at_walls
+        )
+        .is_err());
+    }
+}
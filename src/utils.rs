@@ -1,65 +1,254 @@
+use std::{cmp::Ordering, convert::Infallible, fmt::Debug, ops::Range, thread};
+
+use thiserror::Error;
+
+pub mod columns;
+pub mod cycle;
+pub mod equation;
+pub mod interpreter;
 pub mod numbers;
+pub mod parallel;
+pub mod safety;
+pub mod statistics;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    /// The ordering has no answer for this pair
+    #[error("{0} and {1} can't be compared")]
+    Incomparable(String, String),
+
+    /// The ordering isn't transitive or antisymmetric, so no sorted order exists
+    #[error("Inconsistent ordering between {0} and {1}")]
+    Inconsistent(String, String),
+}
+
+/// Slices shorter than this are sorted on the current thread by the parallel sorts
+const PARALLEL_THRESHOLD: usize = 4096;
+
+pub trait MergeSort<T> {
+    /// In place merge sort which overwrites the original implementing struct. Pairs which
+    /// can't be compared are treated as already in order relative to each other
+    fn merge_sort(&mut self)
+    where
+        T: PartialOrd;
+
+    /// Stable in place merge sort which fails rather than guessing when two elements
+    /// can't be compared. The result is then checked pairwise so orderings which aren't
+    /// transitive are reported too, at the cost of quadratic comparisons. The slice is
+    /// left untouched on error
+    fn try_merge_sort(&mut self) -> Result<(), Error>
+    where
+        T: PartialOrd + Debug;
+
+    /// Stable in place merge sort using a comparator
+    fn merge_sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Stable in place merge sort by a key derived from each element
+    fn merge_sort_by_key<K, F>(&mut self, key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K;
+
+    /// `merge_sort` with large slices split across threads
+    fn par_merge_sort(&mut self)
+    where
+        T: PartialOrd + Sync;
+
+    /// Stable merge sort which sorts the halves of large slices on separate threads,
+    /// up to the available parallelism
+    fn par_merge_sort_by<F>(&mut self, compare: F)
+    where
+        T: Sync,
+        F: Fn(&T, &T) -> Ordering + Sync;
+}
+
+impl<T> MergeSort<T> for [T] {
+    fn merge_sort(&mut self)
+    where
+        T: PartialOrd,
+    {
+        // Only take from the right half when it's known to be smaller
+        self.merge_sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Greater));
+    }
+
+    fn try_merge_sort(&mut self) -> Result<(), Error>
+    where
+        T: PartialOrd + Debug,
+    {
+        let compare = |a: &T, b: &T| {
+            a.partial_cmp(b)
+                .ok_or_else(|| Error::Incomparable(format!("{:?}", a), format!("{:?}", b)))
+        };
+        let mut order = sorted_order(self, compare)?;
+
+        // A single merge pass only compares neighbours, so confirm every pair agrees
+        for (i, first) in order.iter().enumerate() {
+            for second in &order[i + 1..] {
+                let (a, b) = (&self[*first], &self[*second]);
+                let forwards = compare(a, b)?;
+                let backwards = compare(b, a)?;
+                if forwards == Ordering::Greater || backwards != forwards.reverse() {
+                    return Err(Error::Inconsistent(format!("{:?}", a), format!("{:?}", b)));
+                }
+            }
+        }
+
+        apply_order(self, &mut order);
+        Ok(())
+    }
+
+    fn merge_sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let Ok(mut order) = sorted_order(self, |a, b| Ok::<_, Infallible>(compare(a, b)));
+        apply_order(self, &mut order);
+    }
+
+    fn merge_sort_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.merge_sort_by(|a, b| key(a).cmp(&key(b)));
+    }
 
-pub trait MergeSort {
-    /// In place merge sort which overwrites the original implementing struct
-    fn merge_sort(&mut self);
+    fn par_merge_sort(&mut self)
+    where
+        T: PartialOrd + Sync,
+    {
+        self.par_merge_sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Greater));
+    }
+
+    fn par_merge_sort_by<F>(&mut self, compare: F)
+    where
+        T: Sync,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let mut order = par_sorted_order(self, 0..self.len(), &compare, threads);
+        apply_order(self, &mut order);
+    }
 }
 
-impl<T> MergeSort for Vec<T>
+/// `sorted_order` for the positions in `range`, splitting the range in half on to a new
+/// thread while there are `threads` to spare
+fn par_sorted_order<T, F>(
+    values: &[T],
+    range: Range<usize>,
+    compare: &F,
+    threads: usize,
+) -> Vec<usize>
 where
-    T: PartialOrd + Copy,
+    T: Sync,
+    F: Fn(&T, &T) -> Ordering + Sync,
 {
-    /// In-place merge sort which will overwrite the original array.
-    fn merge_sort(&mut self) {
-        // Base case
-        if self.len() < 2 {
-            return;
-        }
+    if threads < 2 || range.len() < PARALLEL_THRESHOLD {
+        let Ok(order) = sorted_order(&values[range.clone()], |a, b| {
+            Ok::<_, Infallible>(compare(a, b))
+        });
+        return order.into_iter().map(|i| i + range.start).collect();
+    }
 
-        // Divide
-        let mut left = Vec::new();
-        let mut right = Vec::new();
+    let mid = range.start + range.len() / 2;
+    let (left, right) = thread::scope(|s| {
+        let left = s.spawn(|| par_sorted_order(values, range.start..mid, compare, threads / 2));
+        let right = par_sorted_order(values, mid..range.end, compare, threads - threads / 2);
+        (left.join().expect("Sorting thread panicked"), right)
+    });
 
-        let mid_point = (self.len() - (self.len() % 2)) / 2;
-        for i in 0..mid_point {
-            left.push(self[i]);
-        }
-        for i in mid_point..self.len() {
-            right.push(self[i])
+    let mut order = vec![0; range.len()];
+    let Ok(()) = merge(values, &left, &right, &mut order, &mut |a, b| {
+        Ok::<_, Infallible>(compare(a, b))
+    });
+    order
+}
+
+/// Positions of `values` in stable sorted order. `order[i]` is the current position of
+/// the element which belongs at i. Sorting positions rather than the elements themselves
+/// means nothing needs to be Copy or Clone
+fn sorted_order<T, E, F>(values: &[T], mut compare: F) -> Result<Vec<usize>, E>
+where
+    F: FnMut(&T, &T) -> Result<Ordering, E>,
+{
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    let mut scratch = vec![0; values.len()];
+
+    // Bottom up: merge neighbouring runs of `width` into runs of twice the size
+    let mut width = 1;
+    while width < values.len() {
+        for start in (0..values.len()).step_by(width * 2) {
+            let mid = (start + width).min(values.len());
+            let end = (start + width * 2).min(values.len());
+            merge(
+                values,
+                &order[start..mid],
+                &order[mid..end],
+                &mut scratch[start..end],
+                &mut compare,
+            )?;
         }
+        std::mem::swap(&mut order, &mut scratch);
+        width *= 2;
+    }
 
-        // Sort
-        left.merge_sort();
-        right.merge_sort();
-
-        // Merge
-        let mut left_pos = 0;
-        let mut right_pos = 0;
-        while (left_pos + right_pos) < self.len() {
-            let val: T;
-            let self_pos = left_pos + right_pos;
-
-            if left_pos == left.len() {
-                val = right[right_pos];
-                right_pos += 1;
-            } else if right_pos == right.len() {
-                val = left[left_pos];
-                left_pos += 1;
-            } else if left[left_pos] <= right[right_pos] {
-                val = left[left_pos];
-                left_pos += 1;
-            } else {
-                val = right[right_pos];
-                right_pos += 1;
-            }
+    Ok(order)
+}
+
+/// Merge two sorted runs of positions into `out`. Ties take from the left run which is
+/// what keeps the sort stable
+fn merge<T, E, F>(
+    values: &[T],
+    left: &[usize],
+    right: &[usize],
+    out: &mut [usize],
+    compare: &mut F,
+) -> Result<(), E>
+where
+    F: FnMut(&T, &T) -> Result<Ordering, E>,
+{
+    let mut left_pos = 0;
+    let mut right_pos = 0;
+    for slot in out.iter_mut() {
+        let take_left = right_pos == right.len()
+            || (left_pos < left.len()
+                && compare(&values[left[left_pos]], &values[right[right_pos]])?
+                    != Ordering::Greater);
+
+        if take_left {
+            *slot = left[left_pos];
+            left_pos += 1;
+        } else {
+            *slot = right[right_pos];
+            right_pos += 1;
+        }
+    }
+    Ok(())
+}
 
-            self[self_pos] = val;
+/// Rearrange `values` so position i holds the element currently at `order[i]`, by
+/// walking each cycle of the permutation with swaps. `order` is used up in the process
+fn apply_order<T>(values: &mut [T], order: &mut [usize]) {
+    for start in 0..values.len() {
+        let mut current = start;
+        while order[current] != start {
+            let source = order[current];
+            values.swap(current, source);
+            // Mark as placed
+            order[current] = current;
+            current = source;
         }
+        order[current] = current;
     }
 }
 
+#[cfg(test)]
 mod test {
-    use crate::utils::MergeSort;
+    use std::cmp::Ordering;
+
+    use crate::utils::{Error, MergeSort};
 
     #[test]
     fn sort_empty_vector() {
@@ -69,7 +258,7 @@ mod test {
 
     #[test]
     fn sort_one_item() {
-        let mut v = vec![1];
+        let mut v = [1];
         v.merge_sort();
     }
 
@@ -106,4 +295,139 @@ mod test {
         v.merge_sort();
         assert_eq!(v, vec!(0, 1, 1));
     }
+
+    #[test]
+    fn sort_by_key_is_stable() {
+        let mut v = vec![(3, 'a'), (1, 'b'), (3, 'c'), (2, 'd'), (1, 'e'), (3, 'f')];
+        v.merge_sort_by_key(|(key, _)| *key);
+        assert_eq!(
+            v,
+            vec!((1, 'b'), (1, 'e'), (2, 'd'), (3, 'a'), (3, 'c'), (3, 'f'))
+        );
+    }
+
+    #[test]
+    fn sort_by_non_copy_values() {
+        let mut v: Vec<String> = ["pear", "fig", "banana", "kiwi", "apple"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        v.merge_sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| b.cmp(a)));
+        assert_eq!(v, vec!("fig", "pear", "kiwi", "apple", "banana"));
+
+        v[1..4].merge_sort();
+        assert_eq!(v, vec!("fig", "apple", "kiwi", "pear", "banana"));
+    }
+
+    #[test]
+    fn sort_matches_std_stable_sort() {
+        // A simple LCG so the test doesn't need a random number crate
+        let mut seed: u64 = 12345;
+        let mut v: Vec<(u8, usize)> = (0..1000)
+            .map(|i| {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                ((seed >> 59) as u8, i)
+            })
+            .collect();
+        let mut expected = v.clone();
+        expected.sort_by_key(|(key, _)| *key);
+
+        v.merge_sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(v, expected);
+    }
+
+    #[test]
+    fn incomparable_values_take_from_the_right() {
+        let mut v = [2.0, f64::NAN, 1.0];
+        v.merge_sort();
+        assert_eq!(1.0, v[0]);
+        assert!(v[1].is_nan());
+        assert_eq!(2.0, v[2]);
+    }
+
+    #[test]
+    fn try_sort_total_order() {
+        let mut v = vec![3, 1, 2, 1];
+        assert_eq!(Ok(()), v.try_merge_sort());
+        assert_eq!(v, vec!(1, 1, 2, 3));
+    }
+
+    #[test]
+    fn try_sort_names_incomparable_pair() {
+        let mut v = [2.0, f64::NAN, 1.0];
+        assert_eq!(
+            Err(Error::Incomparable("2.0".to_string(), "NaN".to_string())),
+            v.try_merge_sort()
+        );
+        // Untouched on failure
+        assert_eq!(2.0, v[0]);
+    }
+
+    /// Rock, paper, scissors. Every pair compares but there's no consistent order
+    #[derive(Debug, PartialEq)]
+    enum Hand {
+        Rock,
+        Paper,
+        Scissors,
+    }
+
+    impl PartialOrd for Hand {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            use Hand::*;
+            Some(match (self, other) {
+                (Rock, Scissors) | (Scissors, Paper) | (Paper, Rock) => Ordering::Greater,
+                (a, b) if a == b => Ordering::Equal,
+                _ => Ordering::Less,
+            })
+        }
+    }
+
+    #[test]
+    fn try_sort_detects_non_transitive_order() {
+        let mut v = vec![Hand::Rock, Hand::Paper, Hand::Scissors];
+        assert!(matches!(v.try_merge_sort(), Err(Error::Inconsistent(_, _))));
+        assert_eq!(v, vec!(Hand::Rock, Hand::Paper, Hand::Scissors));
+    }
+
+    /// Claims everything is smaller than everything else
+    #[derive(Debug, PartialEq)]
+    struct AlwaysLess(u8);
+
+    impl PartialOrd for AlwaysLess {
+        fn partial_cmp(&self, _: &Self) -> Option<Ordering> {
+            Some(Ordering::Less)
+        }
+    }
+
+    #[test]
+    fn try_sort_detects_asymmetric_order() {
+        let mut v = [AlwaysLess(1), AlwaysLess(2)];
+        assert_eq!(
+            Err(Error::Inconsistent(
+                "AlwaysLess(1)".to_string(),
+                "AlwaysLess(2)".to_string()
+            )),
+            v.try_merge_sort()
+        );
+    }
+
+    #[test]
+    fn parallel_sort_matches_serial() {
+        let mut seed: u64 = 987654321;
+        let mut v: Vec<(u16, usize)> = (0..100_000)
+            .map(|i| {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                ((seed >> 54) as u16, i)
+            })
+            .collect();
+        let mut expected = v.clone();
+        expected.merge_sort_by_key(|(key, _)| *key);
+
+        v.par_merge_sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(v, expected);
+
+        let mut small = vec![3, 1, 2];
+        small.par_merge_sort();
+        assert_eq!(small, vec!(1, 2, 3));
+    }
 }
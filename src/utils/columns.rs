@@ -0,0 +1,94 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("Line {line} has {found} columns but {expected} were expected")]
+    ColumnCount {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("Couldn't parse {value:?} in column {column} of line {line}")]
+    Parse {
+        line: usize,
+        column: usize,
+        value: String,
+    },
+}
+
+/// Split each line on whitespace into exactly `N` values and collect them column by
+/// column. Blank lines are skipped. Lines and columns in errors count from one
+pub fn read_columns<T, const N: usize, L, S>(lines: L) -> Result<[Vec<T>; N], Error>
+where
+    T: FromStr,
+    L: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut columns: [Vec<T>; N] = std::array::from_fn(|_| Vec::new());
+
+    for (index, line) in lines.into_iter().enumerate() {
+        let line = line.as_ref();
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != N {
+            return Err(Error::ColumnCount {
+                line: index + 1,
+                expected: N,
+                found: fields.len(),
+            });
+        }
+
+        for (column, (field, values)) in fields.into_iter().zip(&mut columns).enumerate() {
+            let value = field.parse().map_err(|_| Error::Parse {
+                line: index + 1,
+                column: column + 1,
+                value: field.to_string(),
+            })?;
+            values.push(value);
+        }
+    }
+
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reads_columns() -> Result<(), Error> {
+        let [left, right] = read_columns::<i32, 2, _, _>(["3   4", "", "  40\t-3 "])?;
+        assert_eq!(vec![3, 40], left);
+        assert_eq!(vec![4, -3], right);
+
+        let [only] = read_columns::<u8, 1, _, _>(["7", "8"])?;
+        assert_eq!(vec![7, 8], only);
+        Ok(())
+    }
+
+    #[test]
+    fn reports_bad_lines() {
+        assert_eq!(
+            Err(Error::ColumnCount {
+                line: 2,
+                expected: 2,
+                found: 3
+            }),
+            read_columns::<i32, 2, _, _>(["1 2", "1 2 3"])
+        );
+        assert_eq!(
+            Err(Error::Parse {
+                line: 1,
+                column: 2,
+                value: "x".to_string()
+            }),
+            read_columns::<i32, 2, _, _>(["1 x"])
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Where a sequence of states starts repeating. States `prefix` and `prefix + length`
+/// are the first pair which are equal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Steps taken before entering the cycle
+    pub prefix: usize,
+
+    /// Steps taken to go once round the cycle
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step which lands on the same state as `step`
+    pub fn equivalent_step(&self, step: usize) -> usize {
+        if step < self.prefix {
+            step
+        } else {
+            self.prefix + (step - self.prefix) % self.length
+        }
+    }
+}
+
+/// Floyd's tortoise and hare. Constant memory but steps each state up to three times.
+/// `step` returning None ends the sequence, so there's no cycle
+pub fn floyd<S, F>(start: S, mut step: F) -> Option<Cycle>
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> Option<S>,
+{
+    // The hare moves twice as fast so they meet somewhere inside the cycle
+    let mut tortoise = step(&start)?;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        let halfway = step(&hare)?;
+        hare = step(&halfway)?;
+    }
+
+    // The meeting point is a multiple of the cycle length from the start, so walking
+    // one from the start and one from the meeting point at the same pace meets at the
+    // entrance
+    let mut prefix = 0;
+    let mut tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        prefix += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    Some(Cycle { prefix, length })
+}
+
+/// Brent's algorithm. Constant memory like `floyd` but with fewer steps
+pub fn brent<S, F>(start: S, mut step: F) -> Option<Cycle>
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> Option<S>,
+{
+    // Teleport the tortoise to the hare at each power of two until the hare catches it
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start)?;
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    // With the hare a cycle length ahead they meet at the entrance
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..length {
+        hare = step(&hare)?;
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        prefix += 1;
+    }
+
+    Some(Cycle { prefix, length })
+}
+
+/// Remembers every state so each is only stepped once. Fastest when the states are
+/// cheap to hash and the cycle is reached quickly
+pub fn find_cycle<S, F>(start: S, mut step: F) -> Option<Cycle>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> Option<S>,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut state = start;
+    let mut index = 0;
+    loop {
+        if let Some(first) = seen.insert(state.clone(), index) {
+            return Some(Cycle {
+                prefix: first,
+                length: index - first,
+            });
+        }
+        state = step(&state)?;
+        index += 1;
+    }
+}
+
+/// The state after `steps` steps, skipping whole laps once a cycle is found so
+/// billions of steps only cost as much as reaching the cycle. None if the sequence
+/// ends first
+pub fn fast_forward<S, F>(start: S, mut step: F, steps: usize) -> Option<S>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> Option<S>,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut history: Vec<S> = Vec::new();
+    let mut state = start;
+    while history.len() < steps {
+        if let Some(first) = seen.get(&state) {
+            let cycle = Cycle {
+                prefix: *first,
+                length: history.len() - first,
+            };
+            return Some(history[cycle.equivalent_step(steps)].clone());
+        }
+        seen.insert(state.clone(), history.len());
+        history.push(state.clone());
+        state = step(&state)?;
+    }
+    Some(state)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// 0, 1, 2, 3, 4, then 5, 6, 7 forever
+    fn rho(x: &u32) -> Option<u32> {
+        Some(if *x < 7 { x + 1 } else { 5 })
+    }
+
+    /// Counts to ten and stops
+    fn finite(x: &u32) -> Option<u32> {
+        (*x < 10).then_some(x + 1)
+    }
+
+    #[test]
+    fn all_algorithms_agree() {
+        let expected = Some(Cycle {
+            prefix: 5,
+            length: 3,
+        });
+        assert_eq!(expected, floyd(0, rho));
+        assert_eq!(expected, brent(0, rho));
+        assert_eq!(expected, find_cycle(0, rho));
+
+        // Pseudo random sequences give the same answers too
+        for seed in 0..50_u64 {
+            let step = |x: &u64| Some((x * x + 1) % 1009);
+            let expected = find_cycle(seed, step);
+            assert_eq!(expected, floyd(seed, step), "seed {seed}");
+            assert_eq!(expected, brent(seed, step), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn pure_cycles_have_no_prefix() {
+        let step = |x: &u8| Some((x + 1) % 4);
+        let expected = Some(Cycle {
+            prefix: 0,
+            length: 4,
+        });
+        assert_eq!(expected, floyd(0, step));
+        assert_eq!(expected, brent(0, step));
+        assert_eq!(expected, find_cycle(0, step));
+
+        let fixed_point = |x: &u8| Some(*x);
+        assert_eq!(Some(1), brent(9, fixed_point).map(|c| c.length));
+    }
+
+    #[test]
+    fn ending_sequences_have_no_cycle() {
+        assert_eq!(None, floyd(0, finite));
+        assert_eq!(None, brent(0, finite));
+        assert_eq!(None, find_cycle(0, finite));
+    }
+
+    #[test]
+    fn fast_forward_skips_laps() {
+        assert_eq!(Some(0), fast_forward(0, rho, 0));
+        assert_eq!(Some(2), fast_forward(0, rho, 2));
+        assert_eq!(Some(7), fast_forward(0, rho, 1_000_000_000));
+        assert_eq!(Some(5), fast_forward(0, rho, 1_000_000_001));
+        assert_eq!(Some(10), fast_forward(0, finite, 10));
+        assert_eq!(None, fast_forward(0, finite, 11));
+    }
+
+    #[test]
+    fn equivalent_step() {
+        let cycle = Cycle {
+            prefix: 5,
+            length: 3,
+        };
+        assert_eq!(4, cycle.equivalent_step(4));
+        assert_eq!(5, cycle.equivalent_step(8));
+        assert_eq!(7, cycle.equivalent_step(1_000_000_000));
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt::Display;
+
+use strum_macros::{EnumString, VariantArray};
+
+use super::numbers::{concat_digits, count_digits, split_digits};
+
+/// Binary operators for left to right equations. Every intermediate value has to be
+/// a non-negative integer which fits in a u64
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, VariantArray)]
+pub enum Operator {
+    Add,
+    Multiply,
+    /// Append the digits of the right hand side, `12 || 345 = 12345`
+    Concat,
+    Subtract,
+    /// Exact division only, the left hand side has to be a multiple of the right
+    Divide,
+    Xor,
+}
+
+/// What the left hand side of `lhs op rhs = target` could have been
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Preimage {
+    None,
+    One(u64),
+    /// Multiplying by zero forgets the left hand side
+    Any,
+}
+
+impl Operator {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Operator::Add => "+",
+            Operator::Multiply => "*",
+            Operator::Concat => "||",
+            Operator::Subtract => "-",
+            Operator::Divide => "/",
+            Operator::Xor => "^",
+        }
+    }
+
+    /// `lhs op rhs`, None when the result isn't a valid intermediate value
+    pub fn apply(self, lhs: u64, rhs: u64) -> Option<u64> {
+        match self {
+            Operator::Add => lhs.checked_add(rhs),
+            Operator::Multiply => lhs.checked_mul(rhs),
+            Operator::Concat => concat_digits(lhs, rhs),
+            Operator::Subtract => lhs.checked_sub(rhs),
+            Operator::Divide => (rhs != 0 && lhs.is_multiple_of(rhs)).then(|| lhs / rhs),
+            Operator::Xor => Some(lhs ^ rhs),
+        }
+    }
+
+    // Private methods
+
+    /// Undo the operator. This is where the backwards search prunes: sums can't go below
+    /// zero, products need a divisor and concatenations need a matching suffix
+    fn invert(self, target: u64, rhs: u64) -> Preimage {
+        let lhs = match self {
+            Operator::Add => target.checked_sub(rhs),
+            Operator::Multiply if rhs == 0 => {
+                return match target {
+                    0 => Preimage::Any,
+                    _ => Preimage::None,
+                };
+            }
+            Operator::Multiply => target.is_multiple_of(rhs).then(|| target / rhs),
+            // When 10 ^ digits overflows, concatenating on to anything overflows too
+            Operator::Concat => split_digits(target, count_digits(rhs))
+                .and_then(|(high, low)| (low == rhs).then_some(high)),
+            Operator::Subtract => target.checked_add(rhs),
+            Operator::Divide if rhs == 0 => None,
+            Operator::Divide => target.checked_mul(rhs),
+            Operator::Xor => Some(target ^ rhs),
+        };
+
+        match lhs {
+            Some(lhs) => Preimage::One(lhs),
+            None => Preimage::None,
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// Inputs joined by operators and evaluated strictly left to right
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expression {
+    pub inputs: Vec<u64>,
+
+    /// One fewer than the inputs. `operators[i]` sits between inputs i and i + 1
+    pub operators: Vec<Operator>,
+}
+
+impl Expression {
+    pub fn evaluate(&self) -> Option<u64> {
+        let (first, rest) = self.inputs.split_first()?;
+        rest.iter()
+            .zip(&self.operators)
+            .try_fold(*first, |acc, (input, operator)| operator.apply(acc, *input))
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut inputs = self.inputs.iter();
+        if let Some(first) = inputs.next() {
+            write!(f, "{first}")?;
+        }
+        for (input, operator) in inputs.zip(&self.operators) {
+            write!(f, " {operator} {input}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds operators which make a list of inputs evaluate to a target. Works backwards
+/// from the target by undoing the final operator, so impossible branches are cut as
+/// soon as an inverse fails and values only shrink for the usual operators.
+#[derive(Debug, Clone)]
+pub struct Solver {
+    operators: Vec<Operator>,
+}
+
+impl Solver {
+    /// Operators are tried in the given order so earlier ones are preferred
+    pub fn new(operators: &[Operator]) -> Self {
+        Solver {
+            operators: operators.to_vec(),
+        }
+    }
+
+    /// The first expression found which evaluates to `target`. None if no combination
+    /// of operators works or there are no inputs
+    pub fn solve(&self, target: u64, inputs: &[u64]) -> Option<Expression> {
+        if inputs.is_empty() {
+            return None;
+        }
+
+        let mut operators = Vec::with_capacity(inputs.len() - 1);
+        if !self.solve_backwards(target, inputs, &mut operators) {
+            return None;
+        }
+
+        // Found from the last operator to the first
+        operators.reverse();
+        let expression = Expression {
+            inputs: inputs.to_vec(),
+            operators,
+        };
+        debug_assert_eq!(Some(target), expression.evaluate(), "{expression}");
+        Some(expression)
+    }
+
+    pub fn solvable(&self, target: u64, inputs: &[u64]) -> bool {
+        self.solve(target, inputs).is_some()
+    }
+
+    // Private methods
+
+    /// Pushes the operators used, last first, when successful
+    fn solve_backwards(&self, target: u64, inputs: &[u64], operators: &mut Vec<Operator>) -> bool {
+        let Some((last, rest)) = inputs.split_last() else {
+            return false;
+        };
+        if rest.is_empty() {
+            return *last == target;
+        }
+
+        for operator in &self.operators {
+            operators.push(*operator);
+            let found = match operator.invert(target, *last) {
+                Preimage::None => false,
+                Preimage::One(lhs) => self.solve_backwards(lhs, rest, operators),
+                Preimage::Any => self.solve_forwards(rest, operators),
+            };
+            if found {
+                return true;
+            }
+            operators.pop();
+        }
+
+        false
+    }
+
+    /// Any valid evaluation of the inputs will do. Pushes the operators used, last
+    /// first, when successful
+    fn solve_forwards(&self, inputs: &[u64], operators: &mut Vec<Operator>) -> bool {
+        fn search(solver: &Solver, acc: u64, inputs: &[u64], chosen: &mut Vec<Operator>) -> bool {
+            let Some((next, rest)) = inputs.split_first() else {
+                return true;
+            };
+            for operator in &solver.operators {
+                if let Some(value) = operator.apply(acc, *next) {
+                    chosen.push(*operator);
+                    if search(solver, value, rest, chosen) {
+                        return true;
+                    }
+                    chosen.pop();
+                }
+            }
+            false
+        }
+
+        let mut chosen = Vec::new();
+        if !search(self, inputs[0], &inputs[1..], &mut chosen) {
+            return false;
+        }
+        operators.extend(chosen.into_iter().rev());
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use strum::VariantArray;
+
+    use super::*;
+
+    const ADD_MUL: [Operator; 2] = [Operator::Add, Operator::Multiply];
+    const ADD_MUL_CONCAT: [Operator; 3] = [Operator::Add, Operator::Multiply, Operator::Concat];
+
+    #[test]
+    fn finds_expressions() {
+        let solver = Solver::new(&ADD_MUL);
+        let expression = solver.solve(3267, &[81, 40, 27]).unwrap();
+        assert_eq!("81 * 40 + 27", expression.to_string());
+        assert_eq!(Some(3267), expression.evaluate());
+
+        assert_eq!(None, solver.solve(156, &[15, 6]));
+        assert_eq!(None, solver.solve(7290, &[6, 8, 6, 15]));
+    }
+
+    #[test]
+    fn concatenation() {
+        let solver = Solver::new(&ADD_MUL_CONCAT);
+        assert_eq!("15 || 6", solver.solve(156, &[15, 6]).unwrap().to_string());
+        assert_eq!(
+            "6 * 8 || 6 * 15",
+            solver.solve(7290, &[6, 8, 6, 15]).unwrap().to_string()
+        );
+        assert!(!solver.solvable(161011, &[16, 10, 13]));
+        assert!(solver.solvable(10, &[0, 10]));
+    }
+
+    #[test]
+    fn concatenation_inverse_agrees_with_apply() {
+        // 10 ^ 20 doesn't fit so nothing can have these digits appended
+        assert_eq!(None, Operator::Concat.apply(0, u64::MAX));
+        assert_eq!(Preimage::None, Operator::Concat.invert(u64::MAX, u64::MAX));
+        assert!(!Solver::new(&[Operator::Concat]).solvable(u64::MAX, &[0, u64::MAX]));
+
+        // Nineteen digits is the longest suffix which can still be appended
+        let longest = 10_u64.pow(18);
+        assert_eq!(Some(longest), Operator::Concat.apply(0, longest));
+        assert_eq!(Preimage::One(0), Operator::Concat.invert(longest, longest));
+    }
+
+    #[test]
+    fn other_operators() {
+        let solver = Solver::new(&[Operator::Subtract, Operator::Divide, Operator::Xor]);
+        assert_eq!(
+            "20 - 5 / 3",
+            solver.solve(5, &[20, 5, 3]).unwrap().to_string()
+        );
+        assert_eq!("6 ^ 3", solver.solve(5, &[6, 3]).unwrap().to_string());
+        // 7 / 2 isn't exact
+        assert!(!Solver::new(&[Operator::Divide]).solvable(3, &[7, 2]));
+        // 3 - 5 would go negative
+        assert!(!Solver::new(&[Operator::Subtract]).solvable(0, &[3, 5, 2]));
+    }
+
+    #[test]
+    fn multiplying_by_zero() {
+        let solver = Solver::new(&ADD_MUL);
+        let expression = solver.solve(4, &[9, 9, 0, 4]).unwrap();
+        assert_eq!(Some(4), expression.evaluate());
+        assert!(!solver.solvable(3, &[9, 9, 0, 4]));
+    }
+
+    #[test]
+    fn every_answer_evaluates_to_its_target() {
+        let solver = Solver::new(Operator::VARIANTS);
+        for target in 0..200 {
+            if let Some(expression) = solver.solve(target, &[3, 7, 2, 11]) {
+                assert_eq!(Some(target), expression.evaluate(), "{expression}");
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_inputs() {
+        let solver = Solver::new(&ADD_MUL);
+        assert_eq!(None, solver.solve(0, &[]));
+        assert!(solver.solvable(5, &[5]));
+        assert!(!solver.solvable(5, &[4]));
+    }
+}
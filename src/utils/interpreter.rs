@@ -0,0 +1,613 @@
+use std::{collections::VecDeque, fmt::Display, io::BufRead};
+
+/// Machine state carried from one instruction to the next, and from one line to the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    /// Cleared by `don't()` and set again by `do()`
+    pub enabled: bool,
+
+    /// Sum of every product taken while enabled
+    pub total: u64,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            enabled: true,
+            total: 0,
+        }
+    }
+}
+
+/// What an instruction does to the machine given its operands
+pub type Effect = fn(&mut State, &[u32]);
+
+/// One entry in an instruction table. Matches `name(a,b,...)` with exactly `operands`
+/// comma separated operands of one to three digits and no whitespace anywhere
+#[derive(Debug, Clone, Copy)]
+pub struct InstructionSpec {
+    pub name: &'static str,
+    pub operands: usize,
+    pub effect: Effect,
+}
+
+/// Longest operand accepted, in digits
+pub const MAX_DIGITS: usize = 3;
+
+/// `mul(a,b)` adds `a * b` to the total while enabled
+pub const MUL: InstructionSpec = InstructionSpec {
+    name: "mul",
+    operands: 2,
+    effect: |state, operands| {
+        if state.enabled {
+            state.total += u64::from(operands[0]) * u64::from(operands[1]);
+        }
+    },
+};
+
+pub const DO: InstructionSpec = InstructionSpec {
+    name: "do",
+    operands: 0,
+    effect: |state, _| state.enabled = true,
+};
+
+pub const DONT: InstructionSpec = InstructionSpec {
+    name: "don't",
+    operands: 0,
+    effect: |state, _| state.enabled = false,
+};
+
+/// Multiplications only, everything else is noise
+pub const MULTIPLY_ONLY: [InstructionSpec; 1] = [MUL];
+
+/// Multiplications which can be switched off and on again
+pub const CONDITIONAL: [InstructionSpec; 3] = [MUL, DO, DONT];
+
+/// An instruction found in the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction<'t> {
+    pub name: &'t str,
+    pub operands: Vec<u32>,
+
+    /// Byte offset of the first character of the name within its line
+    pub offset: usize,
+
+    /// Index of the matching spec in the table
+    spec: usize,
+}
+
+/// Something which was nearly an instruction but won't be run. Useful for working out
+/// why a total isn't what was expected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearMiss<'t> {
+    pub name: &'t str,
+
+    /// Byte offset of the first character of the name within its line
+    pub offset: usize,
+
+    /// Everything from the name up to the closing bracket
+    pub text: String,
+
+    pub mistake: Mistake,
+}
+
+/// Why a near miss isn't an instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mistake {
+    /// Brackets other than `(` and `)`, like `mul[3,4]`
+    WrongBrackets,
+
+    /// Spaces between the name, brackets or operands, like `mul(1, 2)`
+    Whitespace,
+
+    OperandCount {
+        expected: usize,
+        found: usize,
+    },
+
+    /// An operand longer than `MAX_DIGITS`
+    TooManyDigits,
+}
+
+impl Display for Mistake {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mistake::WrongBrackets => write!(f, "wrong brackets"),
+            Mistake::Whitespace => write!(f, "whitespace"),
+            Mistake::OperandCount { expected, found } => {
+                write!(f, "expected {expected} operands but found {found}")
+            }
+            Mistake::TooManyDigits => write!(f, "operands can be at most {MAX_DIGITS} digits"),
+        }
+    }
+}
+
+/// Either an instruction or, when asked for, a near miss
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token<'t> {
+    Instruction(Instruction<'t>),
+    NearMiss(NearMiss<'t>),
+}
+
+/// Finds every well formed instruction in a line, skipping over anything else. A
+/// match is consumed whole so instructions never overlap
+pub struct Tokenizer<'t, 's> {
+    table: &'t [InstructionSpec],
+    line: &'s [u8],
+    offset: usize,
+    near_misses: bool,
+}
+
+impl<'t, 's> Tokenizer<'t, 's> {
+    pub fn new(table: &'t [InstructionSpec], line: &'s str) -> Self {
+        Self::from_bytes(table, line.as_bytes())
+    }
+
+    /// The line doesn't have to be valid UTF-8, anything which isn't part of an
+    /// instruction is skipped byte by byte
+    pub fn from_bytes(table: &'t [InstructionSpec], line: &'s [u8]) -> Self {
+        Tokenizer {
+            table,
+            line,
+            offset: 0,
+            near_misses: false,
+        }
+    }
+
+    /// Also look for near misses. They're only returned by `next_token`
+    pub fn with_near_misses(mut self) -> Self {
+        self.near_misses = true;
+        self
+    }
+
+    /// The next instruction, or near miss if they're wanted
+    pub fn next_token(&mut self) -> Option<Token<'t>> {
+        while self.offset < self.line.len() {
+            if let Some((instruction, end)) = self.instruction_at(self.offset) {
+                self.offset = end;
+                return Some(Token::Instruction(instruction));
+            }
+            if self.near_misses {
+                if let Some((near_miss, end)) = self.near_miss_at(self.offset) {
+                    self.offset = end;
+                    return Some(Token::NearMiss(near_miss));
+                }
+            }
+            self.offset += 1;
+        }
+        None
+    }
+
+    // Private methods
+
+    /// The instruction starting exactly at `offset` and where it ends
+    fn instruction_at(&self, offset: usize) -> Option<(Instruction<'t>, usize)> {
+        let rest = &self.line[offset..];
+        self.table.iter().enumerate().find_map(|(index, spec)| {
+            let arguments = rest
+                .strip_prefix(spec.name.as_bytes())?
+                .strip_prefix(b"(")?;
+            let (operands, length) = parse_operands(arguments, spec.operands)?;
+            let end = offset + spec.name.len() + 1 + length;
+            Some((
+                Instruction {
+                    name: spec.name,
+                    operands,
+                    offset,
+                    spec: index,
+                },
+                end,
+            ))
+        })
+    }
+
+    /// A name followed by something shaped like an operand list which doesn't parse.
+    /// Only digits, commas and spaces may sit between the brackets, which keeps noise
+    /// like `do_not_mul` or `mul(32,64!` from being reported
+    fn near_miss_at(&self, offset: usize) -> Option<(NearMiss<'t>, usize)> {
+        let rest = &self.line[offset..];
+        self.table.iter().find_map(|spec| {
+            let after = rest.strip_prefix(spec.name.as_bytes())?;
+            let open = after.iter().position(|byte| *byte != b' ')?;
+            if !b"([{<".contains(&after[open]) {
+                return None;
+            }
+            let close = open
+                + 1
+                + after[open + 1..]
+                    .iter()
+                    .position(|byte| !(byte.is_ascii_digit() || b", ".contains(byte)))?;
+            if !b")]}>".contains(&after[close]) {
+                return None;
+            }
+
+            let inner = &after[open + 1..close];
+            let operands: Vec<&[u8]> = if inner.iter().all(|byte| *byte == b' ') {
+                Vec::new()
+            } else {
+                inner
+                    .split(|byte| *byte == b',')
+                    .map(|o| o.trim_ascii())
+                    .collect()
+            };
+            if operands
+                .iter()
+                .any(|o| o.is_empty() || !o.iter().all(u8::is_ascii_digit))
+            {
+                return None;
+            }
+
+            let mistake = if after[open] != b'(' || after[close] != b')' {
+                Mistake::WrongBrackets
+            } else if open > 0 || inner.contains(&b' ') {
+                Mistake::Whitespace
+            } else if operands.len() != spec.operands {
+                Mistake::OperandCount {
+                    expected: spec.operands,
+                    found: operands.len(),
+                }
+            } else if operands.iter().any(|o| o.len() > MAX_DIGITS) {
+                Mistake::TooManyDigits
+            } else {
+                return None;
+            };
+
+            let end = offset + spec.name.len() + close + 1;
+            Some((
+                NearMiss {
+                    name: spec.name,
+                    offset,
+                    text: String::from_utf8_lossy(&self.line[offset..end]).into_owned(),
+                    mistake,
+                },
+                end,
+            ))
+        })
+    }
+}
+
+impl<'t> Iterator for Tokenizer<'t, '_> {
+    type Item = Instruction<'t>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_token()? {
+                Token::Instruction(instruction) => return Some(instruction),
+                Token::NearMiss(_) => continue,
+            }
+        }
+    }
+}
+
+/// Parses `a,b,...)` with exactly `count` operands. Returns the operands and the number
+/// of bytes used, including the closing bracket
+fn parse_operands(bytes: &[u8], count: usize) -> Option<(Vec<u32>, usize)> {
+    let mut operands = Vec::with_capacity(count);
+    let mut used = 0;
+    for index in 0..count {
+        if index > 0 {
+            if bytes.get(used) != Some(&b',') {
+                return None;
+            }
+            used += 1;
+        }
+
+        let digits = bytes[used..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        if !(1..=MAX_DIGITS).contains(&digits) {
+            return None;
+        }
+        let operand = bytes[used..used + digits]
+            .iter()
+            .fold(0, |acc, digit| acc * 10 + u32::from(digit - b'0'));
+        operands.push(operand);
+        used += digits;
+    }
+
+    (bytes.get(used) == Some(&b')')).then_some((operands, used + 1))
+}
+
+/// Runs the instructions of each line in turn as one program, so a `don't()` at the
+/// end of a line still holds at the start of the next
+#[derive(Debug, Clone)]
+pub struct Interpreter<'t> {
+    table: &'t [InstructionSpec],
+    state: State,
+}
+
+impl<'t> Interpreter<'t> {
+    pub fn new(table: &'t [InstructionSpec]) -> Self {
+        Interpreter {
+            table,
+            state: State::default(),
+        }
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn execute(&mut self, instruction: &Instruction<'t>) {
+        (self.table[instruction.spec].effect)(&mut self.state, &instruction.operands);
+    }
+
+    pub fn run_line(&mut self, line: &str) {
+        for instruction in Tokenizer::new(self.table, line) {
+            self.execute(&instruction);
+        }
+    }
+
+    /// Run every line and return the final state
+    pub fn run<L, S>(&mut self, lines: L) -> State
+    where
+        L: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for line in lines {
+            self.run_line(line.as_ref());
+        }
+        self.state
+    }
+}
+
+/// What the scanner saw, in the order it was seen. Lines count from one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<'t> {
+    Executed {
+        line: usize,
+        instruction: Instruction<'t>,
+
+        /// Whether the machine was enabled when the instruction ran, so a disabled
+        /// multiplication added nothing
+        enabled: bool,
+    },
+
+    NearMiss {
+        line: usize,
+        near_miss: NearMiss<'t>,
+    },
+}
+
+/// Interprets a program straight from a reader one line at a time, so only a single
+/// line is ever held in memory. Iterating gives a trace of every instruction run and
+/// every near miss
+pub struct Scanner<'t, R> {
+    interpreter: Interpreter<'t>,
+    reader: R,
+    buffer: Vec<u8>,
+    line: usize,
+    pending: VecDeque<Event<'t>>,
+}
+
+impl<'t, R: BufRead> Scanner<'t, R> {
+    pub fn new(table: &'t [InstructionSpec], reader: R) -> Self {
+        Scanner {
+            interpreter: Interpreter::new(table),
+            reader,
+            buffer: Vec::new(),
+            line: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// The state after everything scanned so far
+    pub fn state(&self) -> State {
+        self.interpreter.state()
+    }
+
+    /// Scan to the end, dropping the trace, and return the final state
+    pub fn run(&mut self) -> std::io::Result<State> {
+        for event in self.by_ref() {
+            event?;
+        }
+        Ok(self.state())
+    }
+
+    // Private methods
+
+    /// Queue up the events for the next line. False at the end of the input
+    fn scan_line(&mut self) -> std::io::Result<bool> {
+        self.buffer.clear();
+        if self.reader.read_until(b'\n', &mut self.buffer)? == 0 {
+            return Ok(false);
+        }
+        self.line += 1;
+
+        let line = self.buffer.strip_suffix(b"\n").unwrap_or(&self.buffer);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let mut tokens = Tokenizer::from_bytes(self.interpreter.table, line).with_near_misses();
+        while let Some(token) = tokens.next_token() {
+            self.pending.push_back(match token {
+                Token::Instruction(instruction) => {
+                    let enabled = self.interpreter.state.enabled;
+                    self.interpreter.execute(&instruction);
+                    Event::Executed {
+                        line: self.line,
+                        instruction,
+                        enabled,
+                    }
+                }
+                Token::NearMiss(near_miss) => Event::NearMiss {
+                    line: self.line,
+                    near_miss,
+                },
+            });
+        }
+        Ok(true)
+    }
+}
+
+impl<'t, R: BufRead> Iterator for Scanner<'t, R> {
+    type Item = std::io::Result<Event<'t>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            match self.scan_line() {
+                Ok(true) => continue,
+                Ok(false) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str =
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+    const EXAMPLE_2: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn tokenizes_instructions() {
+        let found: Vec<(&str, Vec<u32>, usize)> = Tokenizer::new(&CONDITIONAL, EXAMPLE_2)
+            .map(|i| (i.name, i.operands, i.offset))
+            .collect();
+        assert_eq!(
+            vec![
+                ("mul", vec![2, 4], 1),
+                ("don't", vec![], 20),
+                ("mul", vec![5, 5], 28),
+                ("mul", vec![11, 8], 48),
+                ("do", vec![], 59),
+                ("mul", vec![8, 5], 64),
+            ],
+            found
+        );
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!(161, Interpreter::new(&MULTIPLY_ONLY).run([EXAMPLE_1]).total);
+        assert_eq!(161, Interpreter::new(&CONDITIONAL).run([EXAMPLE_1]).total);
+        assert_eq!(48, Interpreter::new(&CONDITIONAL).run([EXAMPLE_2]).total);
+    }
+
+    #[test]
+    fn operands_are_one_to_three_digits() {
+        let run = |line: &str| Interpreter::new(&MULTIPLY_ONLY).run([line]).total;
+        assert_eq!(998001, run("mul(999,999)"));
+        assert_eq!(0, run("mul(1234,5)"));
+        assert_eq!(0, run("mul(,5)"));
+        assert_eq!(0, run("mul(1,2,3)"));
+        assert_eq!(0, run("mul( 1,2)"));
+        assert_eq!(6, run("mul(mul(2,3))"));
+    }
+
+    #[test]
+    fn state_carries_across_lines() {
+        let mut interpreter = Interpreter::new(&CONDITIONAL);
+        let state = interpreter.run(["mul(2,3)don't()", "mul(4,5)", "do()mul(1,7)"]);
+        assert_eq!(
+            State {
+                enabled: true,
+                total: 13
+            },
+            state
+        );
+    }
+
+    #[test]
+    fn extra_instructions() {
+        const CLEAR: InstructionSpec = InstructionSpec {
+            name: "clear",
+            operands: 0,
+            effect: |state, _| state.total = 0,
+        };
+        const SUM3: InstructionSpec = InstructionSpec {
+            name: "sum",
+            operands: 3,
+            effect: |state, operands| {
+                state.total += operands.iter().map(|x| u64::from(*x)).sum::<u64>()
+            },
+        };
+        let table = [MUL, CLEAR, SUM3];
+        let state = Interpreter::new(&table).run(["mul(2,2)clear()sum(1,2,3)sum(1,2)"]);
+        assert_eq!(6, state.total);
+    }
+
+    #[test]
+    fn near_misses() {
+        let line = "mul(1, 2)mul[3,4]mul (5,6)mul(1234,5)mul(7)do(1)mul(32,64!do_not_mul(5,5)";
+        let mut tokens = Tokenizer::new(&CONDITIONAL, line).with_near_misses();
+        let mut found = Vec::new();
+        while let Some(token) = tokens.next_token() {
+            found.push(match token {
+                Token::Instruction(i) => (i.offset, i.name.to_string(), None),
+                Token::NearMiss(n) => (n.offset, n.text, Some(n.mistake)),
+            });
+        }
+
+        let miss = |offset, text: &str, mistake| (offset, text.to_string(), Some(mistake));
+        assert_eq!(
+            vec![
+                miss(0, "mul(1, 2)", Mistake::Whitespace),
+                miss(9, "mul[3,4]", Mistake::WrongBrackets),
+                miss(17, "mul (5,6)", Mistake::Whitespace),
+                miss(26, "mul(1234,5)", Mistake::TooManyDigits),
+                miss(
+                    37,
+                    "mul(7)",
+                    Mistake::OperandCount {
+                        expected: 2,
+                        found: 1
+                    }
+                ),
+                miss(
+                    43,
+                    "do(1)",
+                    Mistake::OperandCount {
+                        expected: 0,
+                        found: 1
+                    }
+                ),
+                (65, "mul".to_string(), None),
+            ],
+            found
+        );
+
+        // Near misses are never run
+        assert_eq!(25, Interpreter::new(&CONDITIONAL).run([line]).total);
+    }
+
+    #[test]
+    fn scanner_traces_a_reader() -> std::io::Result<()> {
+        let input = "mul(2,3)don't()\r\nmul(4,5)mul(1 ,1)\ndo()mul(1,7)";
+        let events: Vec<Event> =
+            Scanner::new(&CONDITIONAL, input.as_bytes()).collect::<Result<_, _>>()?;
+        let trace: Vec<(usize, usize, &str, bool)> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Executed {
+                    line,
+                    instruction,
+                    enabled,
+                } => Some((*line, instruction.offset, instruction.name, *enabled)),
+                Event::NearMiss { .. } => None,
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                (1, 0, "mul", true),
+                (1, 8, "don't", true),
+                (2, 0, "mul", false),
+                (3, 0, "do", false),
+                (3, 4, "mul", true),
+            ],
+            trace
+        );
+        assert!(matches!(
+            &events[3],
+            Event::NearMiss { line: 2, near_miss } if near_miss.offset == 8
+        ));
+
+        let mut scanner = Scanner::new(&CONDITIONAL, input.as_bytes());
+        assert_eq!(13, scanner.run()?.total);
+        Ok(())
+    }
+}
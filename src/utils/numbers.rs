@@ -1,3 +1,17 @@
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{Add, Div, Mul, Rem, Sub},
+    str::FromStr,
+};
+
+pub mod big_int;
+pub mod linear_system;
+pub mod rational;
+pub mod theory;
+
+use big_int::BigInt;
+
 pub trait CheckedDecrement
 where
     Self: Sized,
@@ -5,42 +19,6 @@ where
     fn checked_decrement(&self) -> Option<Self>;
 }
 
-impl CheckedDecrement for usize {
-    fn checked_decrement(&self) -> Option<Self> {
-        self.checked_sub(1)
-    }
-}
-
-impl CheckedDecrement for isize {
-    fn checked_decrement(&self) -> Option<Self> {
-        self.checked_sub(1)
-    }
-}
-
-impl CheckedDecrement for i32 {
-    fn checked_decrement(&self) -> Option<Self> {
-        self.checked_sub(1)
-    }
-}
-
-impl CheckedDecrement for u32 {
-    fn checked_decrement(&self) -> Option<Self> {
-        self.checked_sub(1)
-    }
-}
-
-impl CheckedDecrement for i64 {
-    fn checked_decrement(&self) -> Option<Self> {
-        self.checked_sub(1)
-    }
-}
-
-impl CheckedDecrement for u64 {
-    fn checked_decrement(&self) -> Option<Self> {
-        self.checked_sub(1)
-    }
-}
-
 pub trait CheckedIncrement
 where
     Self: Sized,
@@ -48,42 +26,6 @@ where
     fn checked_increment(&self) -> Option<Self>;
 }
 
-impl CheckedIncrement for usize {
-    fn checked_increment(&self) -> Option<Self> {
-        self.checked_add(1)
-    }
-}
-
-impl CheckedIncrement for isize {
-    fn checked_increment(&self) -> Option<Self> {
-        self.checked_add(1)
-    }
-}
-
-impl CheckedIncrement for i32 {
-    fn checked_increment(&self) -> Option<Self> {
-        self.checked_add(1)
-    }
-}
-
-impl CheckedIncrement for u32 {
-    fn checked_increment(&self) -> Option<Self> {
-        self.checked_add(1)
-    }
-}
-
-impl CheckedIncrement for i64 {
-    fn checked_increment(&self) -> Option<Self> {
-        self.checked_add(1)
-    }
-}
-
-impl CheckedIncrement for u64 {
-    fn checked_increment(&self) -> Option<Self> {
-        self.checked_add(1)
-    }
-}
-
 pub trait CheckedSub
 where
     Self: Sized,
@@ -91,121 +33,336 @@ where
     fn checked_sub(&self, rhs: Self) -> Option<Self>;
 }
 
-impl CheckedSub for isize {
-    fn checked_sub(&self, rhs: Self) -> Option<Self> {
-        isize::checked_sub(*self, rhs)
-    }
-}
-
-impl CheckedSub for usize {
-    fn checked_sub(&self, rhs: Self) -> Option<Self> {
-        usize::checked_sub(*self, rhs)
-    }
+pub trait CheckedAdd
+where
+    Self: Sized,
+{
+    fn checked_add(&self, rhs: Self) -> Option<Self>;
 }
 
-impl CheckedSub for i32 {
-    fn checked_sub(&self, rhs: Self) -> Option<Self> {
-        i32::checked_sub(*self, rhs)
-    }
+pub trait CheckedMul
+where
+    Self: Sized,
+{
+    fn checked_mul(&self, rhs: Self) -> Option<Self>;
 }
 
-impl CheckedSub for u32 {
-    fn checked_sub(&self, rhs: Self) -> Option<Self> {
-        u32::checked_sub(*self, rhs)
-    }
+pub trait LessThanZero {
+    fn less_than_zero(&self) -> bool;
 }
 
-impl CheckedSub for i64 {
-    fn checked_sub(&self, rhs: Self) -> Option<Self> {
-        i64::checked_sub(*self, rhs)
+/// Everything the grids, coordinates and solvers need from a primitive integer.
+/// Implemented for every signed and unsigned primitive integer type.
+pub trait Integer:
+    Sized
+    + Copy
+    + Hash
+    + Eq
+    + Ord
+    + Default
+    + Debug
+    + Display
+    + FromStr
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + CheckedIncrement
+    + CheckedDecrement
+    + CheckedAdd
+    + CheckedSub
+    + CheckedMul
+    + LessThanZero
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    /// True when the type can hold values below zero
+    const SIGNED: bool;
+
+    /// None when dividing by zero or overflowing (`MIN / -1`)
+    fn checked_div(&self, rhs: Self) -> Option<Self>;
+
+    /// None when dividing by zero or overflowing (`MIN % -1`)
+    fn checked_rem(&self, rhs: Self) -> Option<Self>;
+
+    /// None for values which don't fit in an i128 (large u128s)
+    fn to_i128(&self) -> Option<i128>;
+
+    /// None for values which don't fit in this type
+    fn from_i128(value: i128) -> Option<Self>;
+
+    /// None for negative values or values which don't fit in a usize
+    fn to_usize(&self) -> Option<usize>;
+
+    /// None for values which don't fit in this type
+    fn from_usize(value: usize) -> Option<Self>;
+
+    /// Lossless conversion for exact comparisons of products which may not fit in `Self`
+    fn to_big_int(&self) -> BigInt;
+}
+
+macro_rules! impl_integer {
+    ($signed:expr, $($t:ty),*) => {
+        $(
+            impl CheckedDecrement for $t {
+                fn checked_decrement(&self) -> Option<Self> {
+                    <$t>::checked_sub(*self, 1)
+                }
+            }
+
+            impl CheckedIncrement for $t {
+                fn checked_increment(&self) -> Option<Self> {
+                    <$t>::checked_add(*self, 1)
+                }
+            }
+
+            impl CheckedSub for $t {
+                fn checked_sub(&self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, rhs)
+                }
+            }
+
+            impl CheckedAdd for $t {
+                fn checked_add(&self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(*self, rhs)
+                }
+            }
+
+            impl CheckedMul for $t {
+                fn checked_mul(&self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(*self, rhs)
+                }
+            }
+
+            impl LessThanZero for $t {
+                #[allow(unused_comparisons)]
+                fn less_than_zero(&self) -> bool {
+                    *self < 0
+                }
+            }
+
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+                const SIGNED: bool = $signed;
+
+                fn checked_div(&self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_div(*self, rhs)
+                }
+
+                fn checked_rem(&self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_rem(*self, rhs)
+                }
+
+                fn to_i128(&self) -> Option<i128> {
+                    i128::try_from(*self).ok()
+                }
+
+                fn from_i128(value: i128) -> Option<Self> {
+                    <$t>::try_from(value).ok()
+                }
+
+                fn to_usize(&self) -> Option<usize> {
+                    usize::try_from(*self).ok()
+                }
+
+                fn from_usize(value: usize) -> Option<Self> {
+                    <$t>::try_from(value).ok()
+                }
+
+                fn to_big_int(&self) -> BigInt {
+                    BigInt::from(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(true, i8, i16, i32, i64, i128, isize);
+impl_integer!(false, u8, u16, u32, u64, u128, usize);
+
+/// Greatest common divisor of two non-negative values. `gcd(0, 0)` is zero
+pub fn gcd<T>(a: T, b: T) -> T
+where
+    T: Integer,
+{
+    let mut a = a;
+    let mut b = b;
+    while b != T::ZERO {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
     }
+    a
 }
 
-impl CheckedSub for u64 {
-    fn checked_sub(&self, rhs: Self) -> Option<Self> {
-        u64::checked_sub(*self, rhs)
+/// Number of base 10 digits, ignoring any sign. Zero has one digit
+pub fn count_digits<T>(n: T) -> u32
+where
+    T: Integer,
+{
+    let ten = ten::<T>();
+    let mut remaining = n;
+    let mut count = 1;
+    while remaining / ten != T::ZERO {
+        remaining = remaining / ten;
+        count += 1;
     }
+    count
 }
 
-pub trait CheckedAdd
+/// The base 10 digits, most significant first, ignoring any sign
+pub fn digits<T>(n: T) -> Vec<u8>
 where
-    Self: Sized,
+    T: Integer,
 {
-    fn checked_add(&self, rhs: Self) -> Option<Self>;
-}
-
-impl CheckedAdd for isize {
-    fn checked_add(&self, rhs: Self) -> Option<Self> {
-        isize::checked_add(*self, rhs)
+    let ten = ten::<T>();
+    let mut digits = Vec::new();
+    let mut remaining = n;
+    loop {
+        // Remainders of negative values are negative too
+        let digit = (remaining % ten)
+            .to_i128()
+            .expect("Remainders are single digits")
+            .unsigned_abs() as u8;
+        digits.push(digit);
+        remaining = remaining / ten;
+        if remaining == T::ZERO {
+            break;
+        }
     }
+    digits.reverse();
+    digits
 }
 
-impl CheckedAdd for usize {
-    fn checked_add(&self, rhs: Self) -> Option<Self> {
-        usize::checked_add(*self, rhs)
-    }
+/// Split off the lowest `low_digits` digits. `split_digits(1234, 1)` is `(123, 4)`.
+/// None if `10 ^ low_digits` doesn't fit in the type
+pub fn split_digits<T>(n: T, low_digits: u32) -> Option<(T, T)>
+where
+    T: Integer,
+{
+    let divisor = checked_pow10::<T>(low_digits)?;
+    Some((n / divisor, n % divisor))
 }
 
-impl CheckedAdd for i32 {
-    fn checked_add(&self, rhs: Self) -> Option<Self> {
-        i32::checked_add(*self, rhs)
-    }
+/// Write the digits of `b` after the digits of `a`. `concat_digits(12, 345)` is `12345`.
+/// None if the result overflows
+pub fn concat_digits<T>(a: T, b: T) -> Option<T>
+where
+    T: Integer,
+{
+    a.checked_mul(checked_pow10(count_digits(b))?)?
+        .checked_add(b)
 }
 
-impl CheckedAdd for u32 {
-    fn checked_add(&self, rhs: Self) -> Option<Self> {
-        u32::checked_add(*self, rhs)
-    }
-}
+// Private helpers
 
-impl CheckedAdd for i64 {
-    fn checked_add(&self, rhs: Self) -> Option<Self> {
-        i64::checked_add(*self, rhs)
-    }
+fn ten<T>() -> T
+where
+    T: Integer,
+{
+    T::from_i128(10).expect("Every integer type holds 10")
 }
 
-impl CheckedAdd for u64 {
-    fn checked_add(&self, rhs: Self) -> Option<Self> {
-        u64::checked_add(*self, rhs)
+fn checked_pow10<T>(exponent: u32) -> Option<T>
+where
+    T: Integer,
+{
+    let mut result = T::ONE;
+    for _ in 0..exponent {
+        result = result.checked_mul(ten())?;
     }
+    Some(result)
 }
 
-pub trait LessThanZero {
-    fn less_than_zero(&self) -> bool;
-}
+#[cfg(test)]
+mod test {
+    use super::*;
 
-impl LessThanZero for usize {
-    fn less_than_zero(&self) -> bool {
-        false
+    #[test]
+    fn test_gcd() {
+        assert_eq!(6, gcd(12_u32, 18));
+        assert_eq!(6, gcd(18_u32, 12));
+        assert_eq!(7, gcd(7_i64, 0));
+        assert_eq!(7, gcd(0_i64, 7));
+        assert_eq!(1, gcd(17_usize, 5));
+        assert_eq!(0, gcd(0_i32, 0));
     }
-}
 
-impl LessThanZero for u32 {
-    fn less_than_zero(&self) -> bool {
-        false
+    fn assert_integer<T: Integer>(below_zero: Option<T>) {
+        assert_eq!(Some(T::ONE), T::ZERO.checked_increment());
+        assert_eq!(Some(T::ZERO), T::ONE.checked_decrement());
+        assert_eq!(None, T::MAX.checked_increment());
+        assert_eq!(None, T::MIN.checked_decrement());
+        assert_eq!(None, T::MAX.checked_add(T::ONE));
+        assert_eq!(None, T::MAX.checked_mul(T::ONE + T::ONE));
+        assert_eq!(None, T::ONE.checked_div(T::ZERO));
+        assert_eq!(None, T::ONE.checked_rem(T::ZERO));
+        assert!(!T::ZERO.less_than_zero());
+        assert_eq!(T::SIGNED, T::MIN.less_than_zero());
+        assert_eq!(below_zero.is_some(), T::SIGNED);
+        if let Some(negative) = below_zero {
+            assert!(negative.less_than_zero());
+            assert_eq!(Some(-1), negative.to_i128());
+            assert_eq!(None, negative.to_usize());
+        }
+        assert_eq!(Some(T::ONE), T::from_i128(1));
+        assert_eq!(Some(T::ONE), T::from_usize(1));
+        assert_eq!(Some(1), T::ONE.to_usize());
     }
-}
 
-impl LessThanZero for u64 {
-    fn less_than_zero(&self) -> bool {
-        false
+    #[test]
+    fn every_primitive_is_an_integer() {
+        assert_integer::<i8>(Some(-1));
+        assert_integer::<i16>(Some(-1));
+        assert_integer::<i32>(Some(-1));
+        assert_integer::<i64>(Some(-1));
+        assert_integer::<i128>(Some(-1));
+        assert_integer::<isize>(Some(-1));
+        assert_integer::<u8>(None);
+        assert_integer::<u16>(None);
+        assert_integer::<u32>(None);
+        assert_integer::<u64>(None);
+        assert_integer::<u128>(None);
+        assert_integer::<usize>(None);
     }
-}
 
-impl LessThanZero for isize {
-    fn less_than_zero(&self) -> bool {
-        self < &(0 as isize)
+    #[test]
+    fn conversions_out_of_range() {
+        assert_eq!(None, u128::MAX.to_i128());
+        assert_eq!(None, u8::from_i128(256));
+        assert_eq!(None, i8::from_usize(128));
+        assert_eq!(Some(i128::MIN), i128::MIN.to_i128());
     }
-}
 
-impl LessThanZero for i32 {
-    fn less_than_zero(&self) -> bool {
-        self < &(0 as i32)
+    #[test]
+    fn test_digits() {
+        assert_eq!(1, count_digits(0_u32));
+        assert_eq!(3, count_digits(999_u32));
+        assert_eq!(4, count_digits(1000_u32));
+        assert_eq!(20, count_digits(u64::MAX));
+        assert_eq!(vec![1, 0, 2, 4], digits(1024_u64));
+        assert_eq!(vec![0], digits(0_u8));
+        assert_eq!(vec![1, 0, 2, 4], digits(-1024_i32));
+        assert_eq!(vec![1, 2, 8], digits(i8::MIN));
     }
-}
 
-impl LessThanZero for i64 {
-    fn less_than_zero(&self) -> bool {
-        self < &(0 as i64)
+    #[test]
+    fn test_split_and_concat() {
+        assert_eq!(Some((123, 4)), split_digits(1234_u64, 1));
+        assert_eq!(Some((12, 34)), split_digits(1234_u64, 2));
+        assert_eq!(Some((1000, 0)), split_digits(1000000_u64, 3));
+        assert_eq!(None, split_digits(12_u8, 3));
+
+        assert_eq!(Some(12345), concat_digits(12_u64, 345));
+        assert_eq!(Some(120), concat_digits(12_u64, 0));
+        assert_eq!(None, concat_digits(26_u8, 1));
+        assert_eq!(Some(251), concat_digits(25_u8, 1));
     }
 }
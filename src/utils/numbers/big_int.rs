@@ -0,0 +1,528 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+    str::FromStr,
+};
+
+use thiserror::Error;
+
+use super::{CheckedAdd, CheckedDecrement, CheckedIncrement, CheckedMul, CheckedSub, LessThanZero};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    /// Nothing to parse
+    #[error("Can't parse an integer from an empty string")]
+    Empty,
+
+    /// Only an optional leading '-' followed by 0-9 is accepted
+    #[error("'{0}' is not a decimal digit")]
+    InvalidDigit(char),
+}
+
+/// Signed integer which never overflows. Slower than the primitives so it's meant for
+/// answers and the rare branch which doesn't fit in a u64.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+
+    /// Base 2^32 digits, least significant first. Never has trailing zero limbs so
+    /// zero is an empty vector.
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        BigInt::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt::from_parts(false, self.limbs.clone())
+    }
+
+    pub fn pow(&self, exponent: u32) -> BigInt {
+        let mut result = BigInt::from(1_u8);
+        let mut base = self.clone();
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// The value as an i128 if it fits
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        let magnitude = self
+            .limbs
+            .iter()
+            .rev()
+            .fold(0_u128, |acc, limb| (acc << 32) | *limb as u128);
+
+        match self.negative {
+            true if magnitude == i128::MIN.unsigned_abs() => Some(i128::MIN),
+            true => i128::try_from(magnitude).ok().map(|m| -m),
+            false => i128::try_from(magnitude).ok(),
+        }
+    }
+
+    /// The value as a u64 if it fits
+    pub fn to_u64(&self) -> Option<u64> {
+        u64::try_from(self.to_i128()?).ok()
+    }
+
+    /// Both the quotient and remainder, truncating towards zero like the primitives.
+    /// None when dividing by zero
+    pub fn checked_div_rem(&self, rhs: &BigInt) -> Option<(BigInt, BigInt)> {
+        if rhs.is_zero() {
+            return None;
+        }
+
+        let (quotient, remainder) = div_rem_magnitude(&self.limbs, &rhs.limbs);
+        Some((
+            BigInt::from_parts(self.negative != rhs.negative, quotient),
+            BigInt::from_parts(self.negative, remainder),
+        ))
+    }
+
+    // Private methods
+
+    fn from_parts(negative: bool, limbs: Vec<u32>) -> Self {
+        let mut value = BigInt { negative, limbs };
+        value.normalize();
+        value
+    }
+
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        // There's no negative zero
+        if self.limbs.is_empty() {
+            self.negative = false;
+        }
+    }
+}
+
+macro_rules! impl_from_primitive {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigInt {
+                #[allow(unused_comparisons)]
+                fn from(value: $t) -> Self {
+                    let negative = value < 0;
+                    let mut magnitude = (value as i128).unsigned_abs();
+                    // u128 values above i128::MAX wrap when cast, so read them directly
+                    if !negative && (value as u128) > i128::MAX as u128 {
+                        magnitude = value as u128;
+                    }
+
+                    let mut limbs = Vec::new();
+                    while magnitude > 0 {
+                        limbs.push(magnitude as u32);
+                        magnitude >>= 32;
+                    }
+                    BigInt::from_parts(negative, limbs)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_primitive!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl FromStr for BigInt {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        if digits.is_empty() {
+            return Err(Error::Empty);
+        }
+
+        let mut limbs = Vec::new();
+        for c in digits.chars() {
+            let digit = c.to_digit(10).ok_or(Error::InvalidDigit(c))?;
+            mul_add_small(&mut limbs, 10, digit);
+        }
+
+        Ok(BigInt::from_parts(negative, limbs))
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return f.write_str("0");
+        }
+
+        // Peel off nine decimal digits at a time, least significant first
+        let mut chunks = Vec::new();
+        let mut remaining = self.limbs.clone();
+        while !remaining.is_empty() {
+            chunks.push(div_rem_small(&mut remaining, 1_000_000_000));
+        }
+
+        let mut text = String::new();
+        if self.negative {
+            text.push('-');
+        }
+        let mut chunks = chunks.into_iter().rev();
+        text.push_str(&chunks.next().expect("Non zero").to_string());
+        for chunk in chunks {
+            text.push_str(&format!("{:09}", chunk));
+        }
+        f.write_str(&text)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.limbs, &other.limbs),
+            (true, true) => cmp_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> Self::Output {
+        BigInt::from_parts(!self.negative, self.limbs.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> Self::Output {
+        BigInt::from_parts(!self.negative, self.limbs)
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.negative == rhs.negative {
+            return BigInt::from_parts(self.negative, add_magnitude(&self.limbs, &rhs.limbs));
+        }
+
+        // Signs differ so the larger magnitude decides the sign
+        match cmp_magnitude(&self.limbs, &rhs.limbs) {
+            Ordering::Less => {
+                BigInt::from_parts(rhs.negative, sub_magnitude(&rhs.limbs, &self.limbs))
+            }
+            _ => BigInt::from_parts(self.negative, sub_magnitude(&self.limbs, &rhs.limbs)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + &-rhs
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut limbs = vec![0_u32; self.limbs.len() + rhs.limbs.len()];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0_u64;
+            for (j, b) in rhs.limbs.iter().enumerate() {
+                let current = limbs[i + j] as u64 + (*a as u64) * (*b as u64) + carry;
+                limbs[i + j] = current as u32;
+                carry = current >> 32;
+            }
+            limbs[i + rhs.limbs.len()] = carry as u32;
+        }
+        BigInt::from_parts(self.negative != rhs.negative, limbs)
+    }
+}
+
+/// Panics when dividing by zero, the same as the primitives
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div_rem(rhs)
+            .expect("Attempt to divide by zero")
+            .0
+    }
+}
+
+/// Panics when dividing by zero, the same as the primitives
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.checked_div_rem(rhs)
+            .expect("Attempt to calculate the remainder with a divisor of zero")
+            .1
+    }
+}
+
+/// Lets owned values use the operators implemented on references
+macro_rules! forward_owned_binary_ops {
+    ($(($trait:ident, $method:ident)),*) => {
+        $(
+            impl $trait for BigInt {
+                type Output = BigInt;
+
+                fn $method(self, rhs: Self) -> Self::Output {
+                    (&self).$method(&rhs)
+                }
+            }
+        )*
+    };
+}
+
+forward_owned_binary_ops!((Add, add), (Sub, sub), (Mul, mul), (Div, div), (Rem, rem));
+
+impl CheckedAdd for BigInt {
+    fn checked_add(&self, rhs: Self) -> Option<Self> {
+        Some(self + &rhs)
+    }
+}
+
+impl CheckedSub for BigInt {
+    fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        Some(self - &rhs)
+    }
+}
+
+impl CheckedMul for BigInt {
+    fn checked_mul(&self, rhs: Self) -> Option<Self> {
+        Some(self * &rhs)
+    }
+}
+
+impl CheckedIncrement for BigInt {
+    fn checked_increment(&self) -> Option<Self> {
+        Some(self + &BigInt::from(1_u8))
+    }
+}
+
+impl CheckedDecrement for BigInt {
+    fn checked_decrement(&self) -> Option<Self> {
+        Some(self - &BigInt::from(1_u8))
+    }
+}
+
+impl LessThanZero for BigInt {
+    fn less_than_zero(&self) -> bool {
+        self.negative
+    }
+}
+
+impl std::iter::Sum for BigInt {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(BigInt::zero(), |acc, value| &acc + &value)
+    }
+}
+
+// Magnitude helpers. Every slice is little endian without trailing zeros
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut limbs = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0_u64;
+    for i in 0..a.len().max(b.len()) {
+        let sum = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        limbs.push(sum as u32);
+        carry = sum >> 32;
+    }
+    limbs.push(carry as u32);
+    limbs
+}
+
+/// `a - b` where `a` is at least as large as `b`
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut limbs = Vec::with_capacity(a.len());
+    let mut borrow = 0_i64;
+    for (i, limb) in a.iter().enumerate() {
+        let mut difference = *limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if difference < 0 {
+            difference += 1 << 32;
+            borrow = 1;
+        }
+        limbs.push(difference as u32);
+    }
+    limbs
+}
+
+/// `limbs = limbs * multiplier + addend` in place
+fn mul_add_small(limbs: &mut Vec<u32>, multiplier: u32, addend: u32) {
+    let mut carry = addend as u64;
+    for limb in limbs.iter_mut() {
+        let current = (*limb as u64) * (multiplier as u64) + carry;
+        *limb = current as u32;
+        carry = current >> 32;
+    }
+    if carry > 0 {
+        limbs.push(carry as u32);
+    }
+}
+
+/// Divide in place and return the remainder. Trailing zeros are trimmed
+fn div_rem_small(limbs: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0_u64;
+    for limb in limbs.iter_mut().rev() {
+        let current = (remainder << 32) | *limb as u64;
+        *limb = (current / divisor as u64) as u32;
+        remainder = current % divisor as u64;
+    }
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+    remainder as u32
+}
+
+/// Shift and subtract long division, one bit at a time
+fn div_rem_magnitude(dividend: &[u32], divisor: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if divisor.len() == 1 {
+        let mut quotient = dividend.to_vec();
+        let remainder = div_rem_small(&mut quotient, divisor[0]);
+        return (quotient, vec![remainder]);
+    }
+
+    let mut quotient = vec![0_u32; dividend.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+    for bit in (0..dividend.len() * 32).rev() {
+        // remainder = remainder * 2 + the next bit of the dividend
+        let next_bit = (dividend[bit / 32] >> (bit % 32)) & 1;
+        mul_add_small(&mut remainder, 2, next_bit);
+
+        if cmp_magnitude(&remainder, divisor) != Ordering::Less {
+            remainder = sub_magnitude(&remainder, divisor);
+            while remainder.last() == Some(&0) {
+                remainder.pop();
+            }
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        for s in [
+            "0",
+            "1",
+            "-1",
+            "4294967296",
+            "1000000000",
+            "-123456789012345678901234567890",
+            "340282366920938463463374607431768211456",
+        ] {
+            assert_eq!(s, big(s).to_string());
+        }
+        assert_eq!("0", big("-0").to_string());
+        assert_eq!("7", big("0007").to_string());
+        assert_eq!(Err(Error::Empty), "-".parse::<BigInt>());
+        assert_eq!(Err(Error::InvalidDigit('x')), "12x".parse::<BigInt>());
+    }
+
+    #[test]
+    fn from_primitives() {
+        assert_eq!(big("-128"), BigInt::from(i8::MIN));
+        assert_eq!(big(&u128::MAX.to_string()), BigInt::from(u128::MAX));
+        assert_eq!(big(&i128::MIN.to_string()), BigInt::from(i128::MIN));
+        assert_eq!(Some(i128::MIN), BigInt::from(i128::MIN).to_i128());
+        assert_eq!(None, BigInt::from(u128::MAX).to_i128());
+        assert_eq!(Some(u64::MAX), BigInt::from(u64::MAX).to_u64());
+        assert_eq!(None, BigInt::from(-1).to_u64());
+    }
+
+    #[test]
+    fn arithmetic_matches_i128() {
+        let values: [i128; 8] = [0, 1, -1, 7, -13, 4294967296, -99999999999, 1 << 70];
+        for a in values {
+            for b in values {
+                let (big_a, big_b) = (BigInt::from(a), BigInt::from(b));
+                assert_eq!(BigInt::from(a + b), &big_a + &big_b, "{a} + {b}");
+                assert_eq!(BigInt::from(a - b), &big_a - &big_b, "{a} - {b}");
+                assert_eq!(a.cmp(&b), big_a.cmp(&big_b), "{a} cmp {b}");
+                if let Some(product) = a.checked_mul(b) {
+                    assert_eq!(BigInt::from(product), &big_a * &big_b, "{a} * {b}");
+                }
+                if b != 0 {
+                    assert_eq!(BigInt::from(a / b), &big_a / &big_b, "{a} / {b}");
+                    assert_eq!(BigInt::from(a % b), &big_a % &big_b, "{a} % {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn beyond_the_primitives() {
+        let two = BigInt::from(2);
+        let huge = two.pow(200);
+        assert_eq!(
+            "1606938044258990275541962092341162602522202993782792835301376",
+            huge.to_string()
+        );
+        assert_eq!(two.pow(100), &huge / &two.pow(100));
+        assert_eq!(BigInt::zero(), &huge % &two.pow(100));
+        assert_eq!(BigInt::from(1), &(&huge + &BigInt::from(1)) % &two.pow(100));
+        assert_eq!(None, huge.checked_div_rem(&BigInt::zero()));
+    }
+
+    #[test]
+    fn checked_traits_never_overflow() {
+        let max = BigInt::from(u64::MAX);
+        assert_eq!(Some(big("18446744073709551616")), max.checked_increment());
+        assert_eq!(
+            Some(big("340282366920938463426481119284349108225")),
+            max.checked_mul(max.clone())
+        );
+        assert!(BigInt::from(0)
+            .checked_decrement()
+            .unwrap()
+            .less_than_zero());
+    }
+}
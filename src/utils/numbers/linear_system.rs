@@ -0,0 +1,174 @@
+use thiserror::Error;
+
+use super::{rational::Rational, CheckedMul, CheckedSub, Integer};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    /// Only square systems have a unique solution
+    #[error("Expected a square system but row {row} has {columns} coefficients for {rows} rows")]
+    NotSquare {
+        row: usize,
+        rows: usize,
+        columns: usize,
+    },
+
+    /// There has to be one constant per equation
+    #[error("Expected {expected} constants but found {found}")]
+    ConstantsMismatch { expected: usize, found: usize },
+
+    /// No solution or infinitely many solutions
+    #[error("The system is singular")]
+    Singular,
+
+    /// An intermediate value didn't fit in the integer type
+    #[error("The system overflowed while eliminating")]
+    Overflow,
+}
+
+/// Solve `coefficients * x = constants` exactly with Gaussian elimination. Each row of
+/// `coefficients` is one equation and each column one unknown.
+pub fn solve<T>(coefficients: &[Vec<T>], constants: &[T]) -> Result<Vec<Rational<T>>, Error>
+where
+    T: Integer,
+{
+    let size = coefficients.len();
+    if constants.len() != size {
+        return Err(Error::ConstantsMismatch {
+            expected: size,
+            found: constants.len(),
+        });
+    }
+    if let Some((row, values)) = coefficients
+        .iter()
+        .enumerate()
+        .find(|(_, values)| values.len() != size)
+    {
+        return Err(Error::NotSquare {
+            row,
+            rows: size,
+            columns: values.len(),
+        });
+    }
+
+    // Augmented matrix with the constant as the final column of each row
+    let mut rows: Vec<Vec<Rational<T>>> = coefficients
+        .iter()
+        .zip(constants)
+        .map(|(values, constant)| {
+            values
+                .iter()
+                .chain([constant])
+                .map(|v| Rational::from_integer(*v))
+                .collect()
+        })
+        .collect();
+
+    for column in 0..size {
+        let pivot_row = (column..size)
+            .find(|row| !rows[*row][column].is_zero())
+            .ok_or(Error::Singular)?;
+        rows.swap(column, pivot_row);
+
+        let pivot = rows[column][column];
+        for value in rows[column].iter_mut() {
+            *value = value.checked_div(pivot).ok_or(Error::Overflow)?;
+        }
+
+        // Clear the column from every other row so the matrix ends up as the identity
+        let pivot_values = rows[column].clone();
+        for (row, values) in rows.iter_mut().enumerate() {
+            let factor = values[column];
+            if row == column || factor.is_zero() {
+                continue;
+            }
+            for (value, pivot_value) in values.iter_mut().zip(&pivot_values).skip(column) {
+                let scaled = pivot_value.checked_mul(factor).ok_or(Error::Overflow)?;
+                *value = value.checked_sub(scaled).ok_or(Error::Overflow)?;
+            }
+        }
+    }
+
+    Ok(rows.into_iter().map(|row| row[size]).collect())
+}
+
+/// Like `solve` but only returns the solution if every unknown is a whole number
+pub fn solve_integer<T>(coefficients: &[Vec<T>], constants: &[T]) -> Result<Option<Vec<T>>, Error>
+where
+    T: Integer,
+{
+    Ok(solve(coefficients, constants)?
+        .iter()
+        .map(|value| value.to_integer())
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn two_by_two() -> Result<(), Error> {
+        // A button pressed a times moves (94, 34), b moves (22, 67), prize at (8400, 5400)
+        let coefficients = vec![vec![94_i64, 22], vec![34, 67]];
+        assert_eq!(
+            Some(vec![80, 40]),
+            solve_integer(&coefficients, &[8400, 5400])?
+        );
+        assert_eq!(None, solve_integer(&coefficients, &[8401, 5400])?);
+        Ok(())
+    }
+
+    #[test]
+    fn needs_pivoting_and_fractions() -> Result<(), Error> {
+        let coefficients = vec![vec![0_i32, 2, 1], vec![1, 1, 0], vec![2, 0, 1]];
+        let solution = solve(&coefficients, &[3, 1, 2])?;
+        let expected = vec![
+            Rational::new(1, 4).unwrap(),
+            Rational::new(3, 4).unwrap(),
+            Rational::new(3, 2).unwrap(),
+        ];
+        assert_eq!(expected, solution);
+        Ok(())
+    }
+
+    #[test]
+    fn intersecting_lines() -> Result<(), Error> {
+        // y = 2x + 1 and y = -x + 4 meet at (1, 3)
+        let coefficients = vec![vec![-2_i64, 1], vec![1, 1]];
+        assert_eq!(Some(vec![1, 3]), solve_integer(&coefficients, &[1, 4])?);
+        Ok(())
+    }
+
+    #[test]
+    fn singular_systems() {
+        // Parallel lines
+        let coefficients = vec![vec![1_i64, 2], vec![2, 4]];
+        assert_eq!(Err(Error::Singular), solve(&coefficients, &[3, 6]));
+        assert_eq!(Err(Error::Singular), solve(&coefficients, &[3, 7]));
+    }
+
+    #[test]
+    fn malformed_systems() {
+        assert_eq!(
+            Err(Error::ConstantsMismatch {
+                expected: 2,
+                found: 1
+            }),
+            solve(&[vec![1_i32, 0], vec![0, 1]], &[1])
+        );
+        assert_eq!(
+            Err(Error::NotSquare {
+                row: 1,
+                rows: 2,
+                columns: 1
+            }),
+            solve(&[vec![1_i32, 0], vec![0]], &[1, 1])
+        );
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        let coefficients = vec![vec![1_i8, 100], vec![100, 1]];
+        assert_eq!(Err(Error::Overflow), solve(&coefficients, &[1, 1]));
+    }
+}
@@ -0,0 +1,325 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use super::{gcd, CheckedAdd, CheckedMul, CheckedSub, Integer};
+
+/// An exact fraction. Always stored in lowest terms with a positive denominator so
+/// equal values compare and hash equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T> {
+    numerator: T,
+    denominator: T,
+}
+
+impl<T> Rational<T>
+where
+    T: Integer,
+{
+    /// None for a zero denominator, or when moving the sign to the numerator overflows
+    pub fn new(numerator: T, denominator: T) -> Option<Self> {
+        if denominator == T::ZERO {
+            return None;
+        }
+
+        let mut divisor = gcd(numerator, denominator);
+        // Remainders of negative values keep their sign so the gcd can come back negative
+        if divisor.less_than_zero() {
+            divisor = T::ZERO.checked_sub(divisor)?;
+        }
+        let mut numerator = numerator.checked_div(divisor)?;
+        let mut denominator = denominator.checked_div(divisor)?;
+
+        if denominator.less_than_zero() {
+            numerator = T::ZERO.checked_sub(numerator)?;
+            denominator = T::ZERO.checked_sub(denominator)?;
+        }
+
+        Some(Rational {
+            numerator,
+            denominator,
+        })
+    }
+
+    pub fn from_integer(value: T) -> Self {
+        Rational {
+            numerator: value,
+            denominator: T::ONE,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Rational::from_integer(T::ZERO)
+    }
+
+    pub fn numerator(&self) -> T {
+        self.numerator
+    }
+
+    /// Always positive
+    pub fn denominator(&self) -> T {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == T::ZERO
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == T::ONE
+    }
+
+    /// The value if it's a whole number
+    pub fn to_integer(self) -> Option<T> {
+        self.is_integer().then_some(self.numerator)
+    }
+
+    /// None for zero, which has no reciprocal
+    pub fn checked_recip(&self) -> Option<Self> {
+        Rational::new(self.denominator, self.numerator)
+    }
+
+    /// None when dividing by zero or overflowing
+    pub fn checked_div(&self, rhs: Self) -> Option<Self> {
+        self.checked_mul(rhs.checked_recip()?)
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        Rational::new(T::ZERO.checked_sub(self.numerator)?, self.denominator)
+    }
+}
+
+impl<T> CheckedAdd for Rational<T>
+where
+    T: Integer,
+{
+    fn checked_add(&self, rhs: Self) -> Option<Self> {
+        // Scale by the lcm rather than the product to keep intermediate values small
+        let divisor = gcd(self.denominator, rhs.denominator);
+        let lhs_scale = rhs.denominator / divisor;
+        let rhs_scale = self.denominator / divisor;
+
+        let numerator = self
+            .numerator
+            .checked_mul(lhs_scale)?
+            .checked_add(rhs.numerator.checked_mul(rhs_scale)?)?;
+        Rational::new(numerator, self.denominator.checked_mul(lhs_scale)?)
+    }
+}
+
+impl<T> CheckedSub for Rational<T>
+where
+    T: Integer,
+{
+    fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        let divisor = gcd(self.denominator, rhs.denominator);
+        let lhs_scale = rhs.denominator / divisor;
+        let rhs_scale = self.denominator / divisor;
+
+        let numerator = self
+            .numerator
+            .checked_mul(lhs_scale)?
+            .checked_sub(rhs.numerator.checked_mul(rhs_scale)?)?;
+        Rational::new(numerator, self.denominator.checked_mul(lhs_scale)?)
+    }
+}
+
+impl<T> CheckedMul for Rational<T>
+where
+    T: Integer,
+{
+    fn checked_mul(&self, rhs: Self) -> Option<Self> {
+        // Cancel across the two fractions first so the products only overflow when the
+        // result itself can't be represented
+        let Rational {
+            numerator: a,
+            denominator: b,
+        } = *self;
+        let Rational {
+            numerator: c,
+            denominator: d,
+        } = rhs;
+        let ad = Rational::new(a, d)?;
+        let cb = Rational::new(c, b)?;
+
+        Rational::new(
+            ad.numerator.checked_mul(cb.numerator)?,
+            ad.denominator.checked_mul(cb.denominator)?,
+        )
+    }
+}
+
+/// Panics on overflow, the same as the primitives in debug builds
+impl<T> Add for Rational<T>
+where
+    T: Integer,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("Rational addition overflowed")
+    }
+}
+
+/// Panics on overflow, the same as the primitives in debug builds
+impl<T> Sub for Rational<T>
+where
+    T: Integer,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("Rational subtraction overflowed")
+    }
+}
+
+/// Panics on overflow, the same as the primitives in debug builds
+impl<T> Mul for Rational<T>
+where
+    T: Integer,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("Rational multiplication overflowed")
+    }
+}
+
+/// Panics when dividing by zero or on overflow
+impl<T> Div for Rational<T>
+where
+    T: Integer,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs)
+            .expect("Rational division by zero or overflow")
+    }
+}
+
+/// Panics on overflow
+impl<T> Neg for Rational<T>
+where
+    T: Integer,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("Rational negation overflowed")
+    }
+}
+
+impl<T> Ord for Rational<T>
+where
+    T: Integer,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Denominators are positive so cross multiplying keeps the order. Done on BigInt
+        // so the products can't overflow
+        let lhs = &self.numerator.to_big_int() * &other.denominator.to_big_int();
+        let rhs = &other.numerator.to_big_int() * &self.denominator.to_big_int();
+        lhs.cmp(&rhs)
+    }
+}
+
+impl<T> PartialOrd for Rational<T>
+where
+    T: Integer,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> From<T> for Rational<T>
+where
+    T: Integer,
+{
+    fn from(value: T) -> Self {
+        Rational::from_integer(value)
+    }
+}
+
+impl<T> Display for Rational<T>
+where
+    T: Integer,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn r(numerator: i64, denominator: i64) -> Rational<i64> {
+        Rational::new(numerator, denominator).unwrap()
+    }
+
+    #[test]
+    fn normalized_on_creation() {
+        assert_eq!(r(1, 2), r(2, 4));
+        assert_eq!(r(-1, 2), r(1, -2));
+        assert_eq!(r(1, 2), r(-3, -6));
+        assert_eq!(r(0, 1), r(0, -5));
+        assert_eq!(2, r(-6, -4).denominator());
+        assert_eq!(None, Rational::new(1, 0));
+        // The sign can't move to the numerator
+        assert_eq!(None, Rational::new(1_i8, i8::MIN));
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(r(5, 6), r(1, 2) + r(1, 3));
+        assert_eq!(r(1, 6), r(1, 2) - r(1, 3));
+        assert_eq!(r(1, 6), r(1, 2) * r(1, 3));
+        assert_eq!(r(3, 2), r(1, 2) / r(1, 3));
+        assert_eq!(r(-1, 2), -r(1, 2));
+        assert_eq!(None, r(1, 2).checked_div(Rational::zero()));
+    }
+
+    #[test]
+    fn unsigned() {
+        let half = Rational::new(1_u32, 2).unwrap();
+        let third = Rational::new(1_u32, 3).unwrap();
+        assert_eq!(Rational::new(1, 6), half.checked_sub(third));
+        assert_eq!(None, third.checked_sub(half));
+        assert_eq!(None, half.checked_neg());
+    }
+
+    #[test]
+    fn cancels_before_overflowing() {
+        let big = Rational::new(i64::MAX, 3).unwrap();
+        let small = Rational::new(3, i64::MAX).unwrap();
+        assert_eq!(Some(Rational::from(1)), big.checked_mul(small));
+        assert_eq!(None, big.checked_mul(big));
+    }
+
+    #[test]
+    fn ordering_without_overflow() {
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        let nearly_one = Rational::new(u64::MAX - 1, u64::MAX).unwrap();
+        let nearer_one = Rational::new(u64::MAX - 2, u64::MAX - 1).unwrap();
+        assert!(nearer_one < nearly_one);
+        assert!(nearly_one < Rational::from(1));
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(Some(3), r(6, 2).to_integer());
+        assert_eq!(None, r(7, 2).to_integer());
+        assert_eq!("-7/2", r(7, -2).to_string());
+        assert_eq!("3", r(6, 2).to_string());
+    }
+}
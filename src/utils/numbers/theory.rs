@@ -0,0 +1,359 @@
+use super::{gcd, Integer};
+
+/// Least common multiple of two non-negative values. None if it overflows
+pub fn lcm<T>(a: T, b: T) -> Option<T>
+where
+    T: Integer,
+{
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Solve `a * x + b * y = gcd(a, b)`. Returns `(gcd, x, y)`
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    // Keep the gcd positive no matter the signs of the inputs
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The value `x` in `0..modulus` where `a * x = 1 (mod modulus)`.
+/// None when `a` and `modulus` aren't coprime.
+pub fn mod_inverse<T>(a: T, modulus: T) -> Option<T>
+where
+    T: Integer,
+{
+    let modulus_wide = modulus.to_i128()?;
+    if modulus_wide <= 0 {
+        return None;
+    }
+
+    let (divisor, x, _) = extended_gcd(a.to_i128()?.rem_euclid(modulus_wide), modulus_wide);
+    if divisor != 1 {
+        return None;
+    }
+
+    T::from_i128(x.rem_euclid(modulus_wide))
+}
+
+/// `base ^ exponent (mod modulus)` without overflowing along the way
+pub fn mod_pow<T>(base: T, exponent: T, modulus: T) -> Option<T>
+where
+    T: Integer,
+{
+    let modulus = u128::try_from(modulus.to_i128()?).ok()?;
+    if modulus == 0 {
+        return None;
+    }
+
+    let base = u128::try_from(base.to_i128()?.rem_euclid(modulus as i128)).ok()?;
+    let exponent = u128::try_from(exponent.to_i128()?).ok()?;
+
+    T::from_i128(mod_pow_u128(base, exponent, modulus) as i128)
+}
+
+/// Find `x` which satisfies `x = residue (mod modulus)` for every pair given.
+/// Moduli don't need to be coprime. Returns the smallest non-negative `x` and the
+/// combined modulus, or None when the congruences contradict each other.
+pub fn chinese_remainder<T>(congruences: &[(T, T)]) -> Option<(T, T)>
+where
+    T: Integer,
+{
+    let mut result: i128 = 0;
+    let mut combined: i128 = 1;
+
+    for (residue, modulus) in congruences {
+        let residue = residue.to_i128()?;
+        let modulus = modulus.to_i128()?;
+        if modulus <= 0 {
+            return None;
+        }
+
+        // Solve result + combined * k = residue (mod modulus) for k
+        let (divisor, inverse, _) = extended_gcd(combined, modulus);
+        let difference = residue - result;
+        if difference % divisor != 0 {
+            return None;
+        }
+
+        let step = modulus / divisor;
+        let k = (difference / divisor)
+            .checked_mul(inverse)?
+            .rem_euclid(step);
+        result = result.checked_add(combined.checked_mul(k)?)?;
+        combined = combined.checked_mul(step)?;
+        result = result.rem_euclid(combined);
+    }
+
+    Some((T::from_i128(result)?, T::from_i128(combined)?))
+}
+
+/// Deterministic Miller-Rabin primality test, exact for every u64
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+
+    // These bases are enough to be exact for every 64 bit value
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    for witness in WITNESSES {
+        if n.is_multiple_of(witness) {
+            return n == witness;
+        }
+    }
+
+    let mut odd = n - 1;
+    let mut twos = 0;
+    while odd.is_multiple_of(2) {
+        odd /= 2;
+        twos += 1;
+    }
+
+    let n_wide = n as u128;
+    'witness: for witness in WITNESSES {
+        let mut x = mod_pow_u128(witness as u128, odd as u128, n_wide);
+        if x == 1 || x == n_wide - 1 {
+            continue;
+        }
+        for _ in 1..twos {
+            x = mul_mod_u128(x, x, n_wide);
+            if x == n_wide - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+
+    true
+}
+
+/// Prime factors of `n` with their multiplicity, smallest prime first
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    let mut primes = Vec::new();
+    let mut remaining = n;
+
+    // Cheap trial division takes care of the small factors
+    for p in 2..1000 {
+        if remaining < 2 {
+            break;
+        }
+        while remaining.is_multiple_of(p) {
+            primes.push(p);
+            remaining /= p;
+        }
+    }
+
+    // Anything left has only large factors
+    let mut stack = vec![remaining];
+    while let Some(value) = stack.pop() {
+        if value < 2 {
+            continue;
+        } else if is_prime(value) {
+            primes.push(value);
+        } else {
+            let divisor = pollard_rho(value);
+            stack.push(divisor);
+            stack.push(value / divisor);
+        }
+    }
+
+    primes.sort();
+    let mut factors: Vec<(u64, u32)> = Vec::new();
+    for prime in primes {
+        match factors.last_mut() {
+            Some((p, count)) if *p == prime => *count += 1,
+            _ => factors.push((prime, 1)),
+        }
+    }
+    factors
+}
+
+/// Largest value whose square is no more than `n`. None for negative values
+pub fn isqrt<T>(n: T) -> Option<T>
+where
+    T: Integer,
+{
+    let n = u128::try_from(n.to_i128()?).ok()?;
+    if n < 2 {
+        return T::from_i128(n as i128);
+    }
+
+    // Newton's method from an overestimate converges from above
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+
+    T::from_i128(x as i128)
+}
+
+// Private helpers
+
+fn mul_mod_u128(a: u128, b: u128, modulus: u128) -> u128 {
+    match a.checked_mul(b) {
+        Some(product) => product % modulus,
+        // Double and add so nothing ever exceeds twice the modulus
+        None => {
+            let mut result = 0;
+            let mut a = a % modulus;
+            let mut b = b;
+            while b > 0 {
+                if b & 1 == 1 {
+                    result = add_mod_u128(result, a, modulus);
+                }
+                a = add_mod_u128(a, a, modulus);
+                b >>= 1;
+            }
+            result
+        }
+    }
+}
+
+fn add_mod_u128(a: u128, b: u128, modulus: u128) -> u128 {
+    match a.checked_add(b) {
+        Some(sum) => sum % modulus,
+        None => a.wrapping_add(b).wrapping_sub(modulus),
+    }
+}
+
+fn mod_pow_u128(base: u128, exponent: u128, modulus: u128) -> u128 {
+    let mut result = 1 % modulus;
+    let mut base = base % modulus;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod_u128(result, base, modulus);
+        }
+        base = mul_mod_u128(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Some non-trivial divisor of a composite `n`
+fn pollard_rho(n: u64) -> u64 {
+    if n.is_multiple_of(2) {
+        return 2;
+    }
+
+    let n_wide = n as u128;
+    let mut constant = 1;
+    loop {
+        let step = |x: u128| (mul_mod_u128(x, x, n_wide) + constant) % n_wide;
+        let mut tortoise = 2;
+        let mut hare = 2;
+        let mut divisor = 1;
+        while divisor == 1 {
+            tortoise = step(tortoise);
+            hare = step(step(hare));
+            divisor = gcd(tortoise.abs_diff(hare), n_wide);
+        }
+
+        // A divisor equal to n means this constant failed, try the next one
+        if divisor != n_wide {
+            return divisor as u64;
+        }
+        constant += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(Some(36), lcm(12_u32, 18));
+        assert_eq!(Some(0), lcm(0_u32, 18));
+        assert_eq!(None, lcm(u64::MAX, u64::MAX - 1));
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (-240, 46), (17, 5), (0, 9), (9, 0)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(gcd(a.abs(), b.abs()), g);
+            assert_eq!(g, a * x + b * y);
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(Some(4), mod_inverse(3_i64, 11));
+        assert_eq!(Some(7), mod_inverse(-3_i64, 11));
+        assert_eq!(None, mod_inverse(6_u32, 9));
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(Some(1), mod_pow(2_u64, 10, 1023));
+        assert_eq!(Some(445), mod_pow(4_i32, 13, 497));
+        assert_eq!(Some(0), mod_pow(5_u8, 0, 1));
+        assert_eq!(None, mod_pow(5_u8, 3, 0));
+        // 2^127 - 1 is prime so Fermat's little theorem applies. The intermediate
+        // products don't fit in a u128
+        assert_eq!(Some(1), mod_pow(3_i128, i128::MAX - 1, i128::MAX));
+    }
+
+    #[test]
+    fn test_chinese_remainder() {
+        assert_eq!(
+            Some((23, 105)),
+            chinese_remainder(&[(2_i64, 3), (3, 5), (2, 7)])
+        );
+        // Moduli which share a factor still work when they agree
+        assert_eq!(Some((10, 12)), chinese_remainder(&[(4_u32, 6), (2, 4)]));
+        assert_eq!(None, chinese_remainder(&[(1_u32, 6), (2, 4)]));
+        assert_eq!(Some((0, 1)), chinese_remainder::<u32>(&[]));
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let small_primes: Vec<u64> = (0..50).filter(|n| is_prime(*n)).collect();
+        assert_eq!(
+            vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47],
+            small_primes
+        );
+        assert!(is_prime(18446744073709551557));
+        assert!(!is_prime(18446744073709551557 - 2));
+        // Strong pseudoprime to several small bases
+        assert!(!is_prime(3215031751));
+    }
+
+    #[test]
+    fn test_factorize() {
+        assert_eq!(Vec::<(u64, u32)>::new(), factorize(1));
+        assert_eq!(vec![(2, 3), (3, 2), (5, 1)], factorize(360));
+        assert_eq!(
+            vec![(1000003, 1), (4294967291, 1)],
+            factorize(1000003 * 4294967291)
+        );
+        assert_eq!(vec![(65537, 2)], factorize(65537 * 65537));
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(Some(0), isqrt(0_u32));
+        assert_eq!(Some(1), isqrt(3_u32));
+        assert_eq!(Some(2), isqrt(4_i64));
+        assert_eq!(Some(4294967295), isqrt(u64::MAX));
+        assert_eq!(None, isqrt(-4_i32));
+    }
+}
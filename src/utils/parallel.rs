@@ -0,0 +1,46 @@
+use std::thread;
+
+/// Map every item on a pool of scoped threads, one contiguous chunk per available core.
+/// Results come back in the same order as the items
+pub fn par_map<T, U, F>(items: &[T], f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync,
+{
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    if threads < 2 || items.len() < 2 {
+        return items.iter().map(f).collect();
+    }
+
+    let chunk_size = items.len().div_ceil(threads);
+    let f = &f;
+    thread::scope(|s| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| s.spawn(move || chunk.iter().map(f).collect::<Vec<U>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Worker thread panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keeps_order() {
+        let items: Vec<u64> = (0..10_000).collect();
+        let squares = par_map(&items, |x| x * x);
+        assert_eq!(items.iter().map(|x| x * x).collect::<Vec<_>>(), squares);
+    }
+
+    #[test]
+    fn small_inputs() {
+        assert_eq!(Vec::<u8>::new(), par_map(&[] as &[u8], |x| *x));
+        assert_eq!(vec![2], par_map(&[1], |x| x + 1));
+    }
+}
@@ -0,0 +1,265 @@
+use std::ops::RangeInclusive;
+
+/// Which way the levels of a report are allowed to move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectionPolicy {
+    Increasing,
+    Decreasing,
+
+    /// All increasing or all decreasing, whichever needs fewer removals
+    Monotone,
+
+    /// Each step can go either way
+    Any,
+}
+
+/// Decides whether a report is safe: every step between adjacent levels has a size in
+/// `steps` and goes the way the direction policy allows. Unsafe reports can be made
+/// safe by removing levels, and the checker works out which ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafetyChecker {
+    steps: RangeInclusive<u32>,
+    direction: DirectionPolicy,
+}
+
+impl Default for SafetyChecker {
+    /// Strictly increasing or decreasing by one to three each step
+    fn default() -> Self {
+        SafetyChecker::new(1..=3, DirectionPolicy::Monotone)
+    }
+}
+
+impl SafetyChecker {
+    pub fn new(steps: RangeInclusive<u32>, direction: DirectionPolicy) -> Self {
+        SafetyChecker { steps, direction }
+    }
+
+    pub fn is_safe(&self, report: &[i32]) -> bool {
+        self.removals(report, 0).is_some()
+    }
+
+    pub fn is_safe_with(&self, report: &[i32], tolerance: usize) -> bool {
+        self.removals(report, tolerance).is_some()
+    }
+
+    /// The fewest indices, in increasing order, which have to be removed for the report
+    /// to be safe. None when that takes more than `tolerance` removals. Every report
+    /// keeps at least one level, and a single level is always safe.
+    ///
+    /// Runs in O(n * (tolerance + 1)) so a tolerance of one is linear
+    pub fn removals(&self, report: &[i32], tolerance: usize) -> Option<Vec<usize>> {
+        // Removing every level is already more than enough
+        let tolerance = tolerance.min(report.len());
+        match self.direction {
+            DirectionPolicy::Monotone => {
+                let increasing = self.removals_for(report, tolerance, DirectionPolicy::Increasing);
+                let decreasing = self.removals_for(report, tolerance, DirectionPolicy::Decreasing);
+                match (increasing, decreasing) {
+                    (Some(a), Some(b)) if b.len() < a.len() => Some(b),
+                    (Some(a), _) => Some(a),
+                    (None, b) => b,
+                }
+            }
+            direction => self.removals_for(report, tolerance, direction),
+        }
+    }
+
+    // Private methods
+
+    /// Whether `from` can be followed directly by `to`
+    fn valid_step(&self, from: i32, to: i32, direction: DirectionPolicy) -> bool {
+        let delta = i64::from(to) - i64::from(from);
+        let size = match direction {
+            DirectionPolicy::Increasing => delta,
+            DirectionPolicy::Decreasing => -delta,
+            DirectionPolicy::Monotone | DirectionPolicy::Any => delta.abs(),
+        };
+        u32::try_from(size).is_ok_and(|size| self.steps.contains(&size))
+    }
+
+    /// `removed[i]` is the fewest removals among the first i + 1 levels when level i is
+    /// kept. A kept level's predecessor is at most `tolerance + 1` back, since anything
+    /// further means removing too much in between. Ties go to the nearest predecessor,
+    /// so earlier levels are the ones removed
+    fn removals_for(
+        &self,
+        report: &[i32],
+        tolerance: usize,
+        direction: DirectionPolicy,
+    ) -> Option<Vec<usize>> {
+        if report.is_empty() {
+            return Some(Vec::new());
+        }
+
+        let mut removed: Vec<Option<usize>> = vec![None; report.len()];
+        let mut previous: Vec<Option<usize>> = vec![None; report.len()];
+        for (i, level) in report.iter().enumerate() {
+            // Everything before is removed
+            if i <= tolerance {
+                removed[i] = Some(i);
+            }
+
+            for p in i.saturating_sub(tolerance + 1)..i {
+                let Some(before) = removed[p] else {
+                    continue;
+                };
+                let total = before + (i - p - 1);
+                if total <= tolerance
+                    && removed[i].is_none_or(|best| total <= best)
+                    && self.valid_step(report[p], *level, direction)
+                {
+                    removed[i] = Some(total);
+                    previous[i] = Some(p);
+                }
+            }
+        }
+
+        // Everything after the last kept level is removed
+        let (last, _) = removed
+            .iter()
+            .enumerate()
+            .filter_map(|(i, count)| Some((i, (*count)? + report.len() - 1 - i)))
+            .filter(|(_, total)| *total <= tolerance)
+            .min_by_key(|(_, total)| *total)?;
+
+        let mut kept = vec![false; report.len()];
+        let mut current = Some(last);
+        while let Some(i) = current {
+            kept[i] = true;
+            current = previous[i];
+        }
+        Some((0..report.len()).filter(|i| !kept[*i]).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: [[i32; 5]; 6] = [
+        [7, 6, 4, 2, 1],
+        [1, 2, 7, 8, 9],
+        [9, 7, 6, 2, 1],
+        [1, 3, 2, 4, 5],
+        [8, 6, 4, 4, 1],
+        [1, 3, 6, 7, 9],
+    ];
+
+    #[test]
+    fn example() {
+        let checker = SafetyChecker::default();
+        let safe: Vec<bool> = EXAMPLE.iter().map(|r| checker.is_safe(r)).collect();
+        assert_eq!(vec![true, false, false, false, false, true], safe);
+
+        let removals: Vec<Option<Vec<usize>>> =
+            EXAMPLE.iter().map(|r| checker.removals(r, 1)).collect();
+        assert_eq!(
+            vec![
+                Some(vec![]),
+                None,
+                None,
+                Some(vec![1]),
+                Some(vec![2]),
+                Some(vec![])
+            ],
+            removals
+        );
+    }
+
+    #[test]
+    fn first_and_last_levels_can_go() {
+        let checker = SafetyChecker::default();
+        assert_eq!(Some(vec![0]), checker.removals(&[9, 1, 2, 3], 1));
+        assert_eq!(Some(vec![3]), checker.removals(&[1, 2, 3, 9], 1));
+        assert_eq!(Some(vec![0, 1]), checker.removals(&[5, 9, 1, 2, 3], 2));
+    }
+
+    #[test]
+    fn larger_tolerances() {
+        let checker = SafetyChecker::default();
+        let report = [1, 2, 10, 3, 20, 4, 5];
+        assert_eq!(None, checker.removals(&report, 1));
+        assert_eq!(Some(vec![2, 4]), checker.removals(&report, 2));
+        assert_eq!(Some(vec![2, 4]), checker.removals(&report, 5));
+
+        // Keeping one level is always enough
+        assert!(checker.is_safe_with(&[50, 40, 30, 20, 10], 4));
+        assert!(checker.is_safe(&[7]));
+        assert!(checker.is_safe(&[]));
+
+        // Tolerances beyond the length of the report are fine too
+        assert_eq!(Some(vec![2, 4]), checker.removals(&report, usize::MAX));
+        assert!(checker.is_safe_with(&[], usize::MAX));
+    }
+
+    #[test]
+    fn policies() {
+        let increasing = SafetyChecker::new(1..=3, DirectionPolicy::Increasing);
+        assert!(increasing.is_safe(&[1, 2, 3]));
+        assert!(!increasing.is_safe(&[3, 2, 1]));
+
+        let decreasing = SafetyChecker::new(1..=3, DirectionPolicy::Decreasing);
+        assert!(decreasing.is_safe(&[3, 2, 1]));
+
+        let any = SafetyChecker::new(2..=2, DirectionPolicy::Any);
+        assert!(any.is_safe(&[1, 3, 1, 3]));
+        assert!(!SafetyChecker::default().is_safe(&[1, 3, 1, 3]));
+
+        let flat = SafetyChecker::new(0..=1, DirectionPolicy::Increasing);
+        assert!(flat.is_safe(&[4, 4, 5, 5]));
+        assert!(!SafetyChecker::default().is_safe(&[4, 4, 5, 5]));
+    }
+
+    /// The fewest removals found by trying every level in turn, up to `tolerance` deep
+    fn brute_force(checker: &SafetyChecker, report: &[i32], tolerance: usize) -> Option<usize> {
+        if checker.is_safe(report) {
+            return Some(0);
+        }
+        if tolerance == 0 {
+            return None;
+        }
+        (0..report.len())
+            .filter_map(|skip| {
+                let rest: Vec<i32> = report
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != skip)
+                    .map(|(_, level)| *level)
+                    .collect();
+                brute_force(checker, &rest, tolerance - 1)
+            })
+            .min()
+            .map(|removed| removed + 1)
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let checker = SafetyChecker::default();
+        let mut seed = 7_u32;
+        for _ in 0..500 {
+            let report: Vec<i32> = (0..6)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                    ((seed >> 16) % 8) as i32
+                })
+                .collect();
+            for tolerance in 1..=2 {
+                let removals = checker.removals(&report, tolerance);
+                assert_eq!(
+                    brute_force(&checker, &report, tolerance),
+                    removals.as_ref().map(|removals| removals.len()),
+                    "{report:?} with tolerance {tolerance}"
+                );
+                if let Some(removals) = removals {
+                    let rest: Vec<i32> = report
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| !removals.contains(i))
+                        .map(|(_, level)| *level)
+                        .collect();
+                    assert!(checker.is_safe(&rest), "{report:?} without {removals:?}");
+                }
+            }
+        }
+    }
+}
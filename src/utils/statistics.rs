@@ -0,0 +1,153 @@
+use std::{collections::HashMap, hash::Hash, ops::Deref};
+
+use thiserror::Error;
+
+use super::numbers::{rational::Rational, Integer};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("Columns have different lengths, {left} and {right}")]
+    LengthMismatch { left: usize, right: usize },
+
+    #[error("No values to take statistics of")]
+    Empty,
+
+    #[error("Statistic doesn't fit in the value type")]
+    Overflow,
+}
+
+/// Values kept in ascending order, so pairing columns off and order statistics don't
+/// need a sorted copy every time
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Sorted<T>(Vec<T>);
+
+impl<T: Ord> From<Vec<T>> for Sorted<T> {
+    fn from(mut values: Vec<T>) -> Self {
+        values.sort_unstable();
+        Sorted(values)
+    }
+}
+
+impl<T> Deref for Sorted<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Pair the smallest value of each column, then the next smallest and so on, and add up
+/// how far apart each pair is
+pub fn sorted_distance<T: Integer>(left: &Sorted<T>, right: &Sorted<T>) -> Result<T, Error> {
+    if left.len() != right.len() {
+        return Err(Error::LengthMismatch {
+            left: left.len(),
+            right: right.len(),
+        });
+    }
+
+    left.iter()
+        .zip(right.iter())
+        .try_fold(T::ZERO, |total, (l, r)| {
+            // Largest minus smallest so unsigned types never go below zero, though signed
+            // types can still be too far apart
+            let distance = l.max(r).checked_sub(*l.min(r)).ok_or(Error::Overflow)?;
+            total.checked_add(distance).ok_or(Error::Overflow)
+        })
+}
+
+/// How many times each value appears
+pub fn histogram<T: Hash + Eq + Copy>(values: &[T]) -> HashMap<T, usize> {
+    let mut counts = HashMap::new();
+    for value in values {
+        *counts.entry(*value).or_default() += 1;
+    }
+    counts
+}
+
+/// Every value on the left weighted by how often it appears on the right
+pub fn similarity<T: Integer>(left: &[T], right: &[T]) -> Result<T, Error> {
+    let counts = histogram(right);
+    left.iter().try_fold(T::ZERO, |total, value| {
+        let count = counts.get(value).copied().unwrap_or_default();
+        T::from_usize(count)
+            .and_then(|count| value.checked_mul(count))
+            .and_then(|score| total.checked_add(score))
+            .ok_or(Error::Overflow)
+    })
+}
+
+/// The middle value, or halfway between the middle two for an even count
+pub fn median<T: Integer>(values: &Sorted<T>) -> Result<Rational<T>, Error> {
+    let middle = values.len() / 2;
+    match values.len() {
+        0 => Err(Error::Empty),
+        length if length % 2 == 1 => Ok(Rational::from_integer(values[middle])),
+        _ => values[middle - 1]
+            .checked_add(values[middle])
+            .and_then(|sum| Rational::new(sum, T::ONE + T::ONE))
+            .ok_or(Error::Overflow),
+    }
+}
+
+/// The most common value. Ties go to the smallest
+pub fn mode<T: Integer>(values: &[T]) -> Result<T, Error> {
+    histogram(values)
+        .into_iter()
+        .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+        .map(|(value, _)| value)
+        .ok_or(Error::Empty)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn example() -> (Sorted<i32>, Sorted<i32>) {
+        (vec![3, 4, 2, 1, 3, 3].into(), vec![4, 3, 5, 3, 9, 3].into())
+    }
+
+    #[test]
+    fn distance_and_similarity() -> Result<(), Error> {
+        let (left, right) = example();
+        assert_eq!(11, sorted_distance(&left, &right)?);
+        assert_eq!(31, similarity(&left, &right)?);
+
+        let unsigned: (Sorted<u8>, Sorted<u8>) = (vec![9, 1].into(), vec![2, 5].into());
+        assert_eq!(5, sorted_distance(&unsigned.0, &unsigned.1)?);
+        assert_eq!(
+            Err(Error::LengthMismatch { left: 2, right: 1 }),
+            sorted_distance(&unsigned.0, &vec![1].into())
+        );
+        assert_eq!(
+            Err(Error::Overflow),
+            similarity(&[100_u8], &[100, 100, 100])
+        );
+        assert_eq!(
+            Err(Error::Overflow),
+            sorted_distance(&vec![i32::MIN].into(), &vec![i32::MAX].into())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn order_statistics() -> Result<(), Error> {
+        let (left, right) = example();
+        assert_eq!(Rational::from_integer(3), median(&left)?);
+        assert_eq!(Rational::new(7, 2).unwrap(), median(&right)?);
+        assert_eq!(3, mode(&left)?);
+        assert_eq!(2, mode(&[5, 2, 5, 2, 9])?);
+
+        assert_eq!(Err(Error::Empty), median(&Sorted::<i32>::default()));
+        assert_eq!(Err(Error::Empty), mode::<i32>(&[]));
+        Ok(())
+    }
+
+    #[test]
+    fn histograms() {
+        let counts = histogram(&['a', 'b', 'a']);
+        assert_eq!(Some(&2), counts.get(&'a'));
+        assert_eq!(Some(&1), counts.get(&'b'));
+        assert_eq!(None, counts.get(&'c'));
+    }
+}